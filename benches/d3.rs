@@ -1,4 +1,4 @@
-use aoc_2021::d3;
+use aoc_2021::y2021::d3;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 // Amazingly, d3 part2 original implementation is ~100x faster than the "optimized" version!
@@ -22,12 +22,12 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 //   2 (2.00%) high severe
 
 pub fn bench_d3_part2(c: &mut Criterion) {
-    let input_bvs = d3::input_to_bitvectors("inputs/d3");
+    let input_bvs = d3::input_to_bitvectors("inputs/2021/d3").unwrap();
     c.bench_function("d3 part2", |b| b.iter(|| black_box(d3::d3_part2(&input_bvs))));
 }
 
 pub fn bench_d3_part2_alt2(c: &mut Criterion) {
-    let input_bvs = d3::input_to_bitvectors("inputs/d3");
+    let input_bvs = d3::input_to_bitvectors("inputs/2021/d3").unwrap();
     c.bench_function("d3 part2 alt2", |b| b.iter(|| black_box(d3::d3_part2_alt2(&input_bvs))));
 }
 
@@ -0,0 +1,60 @@
+use aoc_2021::solution::{self, Part};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmark parsing and each part of every registered day, on both the `_test` and full inputs.
+///
+/// Benchmarks are named `<YEAR>/d<DAY>/<step> <input>`, e.g. `2021/d7/part2 full`, and
+/// alternative implementations `<YEAR>/d<DAY>/<step> <variant> <input>`, e.g.
+/// `2021/d3/part2 alt2 full`. To run a subset pass a filter, e.g.
+/// `cargo bench --bench days -- 2021/d7/`.
+///
+/// Benchmarks on v1 solutions of day 7:
+///
+/// d7 part1 test           time:   [5.1057 us 5.1255 us 5.1487 us]
+/// d7 part1                time:   [43.079 us 43.288 us 43.542 us]
+/// d7 part2 test           time:   [5.2489 us 5.2597 us 5.2710 us]
+/// d7 part2                time:   [878.05 us 882.47 us 886.07 us]
+pub fn bench_days(c: &mut Criterion) {
+    for day in solution::days() {
+        let mut group = c.benchmark_group(format!("{}/d{}", day.year(), day.day()));
+        // a few days take seconds per iteration, keep a full run tolerable
+        group.sample_size(10);
+        for (label, source) in [("test", day.test_input()), ("full", day.default_input())] {
+            let content = match source.read() {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("skipping {}: {}", source, e);
+                    continue;
+                }
+            };
+            let parsed = match day.parse(&content) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("skipping {}: {}", source, e);
+                    continue;
+                }
+            };
+            group.bench_function(format!("parse {}", label), |b| {
+                b.iter(|| day.parse(black_box(&content)))
+            });
+            for part in Part::ALL {
+                group.bench_function(format!("part{} {}", part, label), |b| {
+                    b.iter(|| parsed.solve(black_box(part)))
+                });
+            }
+            for (part, name) in day.variants() {
+                group.bench_function(format!("part{} {} {}", part, name, label), |b| {
+                    b.iter(|| parsed.solve_variant(black_box(part), name))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = bench_days
+}
+criterion_main!(benches);
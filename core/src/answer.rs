@@ -0,0 +1,100 @@
+/// A puzzle answer, whatever type a day's solver returns it as.
+///
+/// Every day's answer types convert into [`Answer`], so that the registry, the reports and the
+/// manifest all deal with one type: most answers are integers, a few overflow `i64`, and some,
+/// like day 13's folded paper, are text to be read by a human.
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    /// An integer too big for `i64`
+    Big(i128),
+    /// A single line of text
+    Text(String),
+    /// Text over several lines, e.g. letters drawn in `#`s
+    Multiline(String),
+}
+
+impl Answer {
+    /// The answer as an integer, if it is one
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n as i128),
+            Answer::Big(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) | Answer::Multiline(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_small_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::Int(n.into())
+            }
+        }
+    )*};
+}
+
+macro_rules! from_wide_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                match (i64::try_from(n), i128::try_from(n)) {
+                    (Ok(n), _) => Answer::Int(n),
+                    (_, Ok(n)) => Answer::Big(n),
+                    // only u128 gets here
+                    _ => Answer::Text(n.to_string()),
+                }
+            }
+        }
+    )*};
+}
+
+from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+from_wide_int!(u64, usize, isize, i128, u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        if s.trim_end().contains('\n') {
+            Answer::Multiline(s)
+        } else {
+            Answer::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        s.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::from(7usize), Answer::Int(7));
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_string()));
+        assert_eq!(Answer::from("#.\n.#\n"), Answer::Multiline("#.\n.#\n".to_string()));
+        assert_eq!(Answer::from(u64::MAX).as_int(), Some(18446744073709551615));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("x").as_int(), None);
+    }
+}
@@ -0,0 +1,134 @@
+/// Parse errors rendered like compiler errors, pointing into the input they came from:
+///
+/// ```text
+/// error: expected a number, found "x"
+///  --> inputs/2021/d5:3:10
+///   |
+/// 3 | 0,9 -> 5,x
+///   |          ^ expected a number
+/// ```
+use std::fmt::Write;
+
+use crate::error::{AocError, Span};
+
+/// Line and 1-based column of a parse error in `content`, the whole input it was parsed from
+pub fn locate(e: &AocError, content: &str) -> Option<(usize, usize)> {
+    let (line, span) = match e {
+        AocError::Parse { line, span, .. } => (*line, *span),
+        AocError::ParseMany(errors) => return errors.first().and_then(|e| locate(e, content)),
+        _ => return None,
+    };
+    let raw = source_line(content, line)?;
+    Some((line, underline(raw, span).0 + 1))
+}
+
+/// Render `e`, an error from parsing `content` read from `file`, with the offending text of each
+/// parse error underlined. Other errors are rendered on a single line.
+pub fn render(e: &AocError, file: &str, content: &str) -> String {
+    let errors = e.errors();
+    let width = errors
+        .iter()
+        .filter_map(|e| e.line())
+        .max()
+        .map_or(1, |n| n.to_string().len());
+    let mut out = String::new();
+    for e in errors {
+        render_one(&mut out, e, file, content, width);
+    }
+    if errors.len() > 1 {
+        writeln!(out, "error: aborting due to {} previous errors", errors.len()).unwrap();
+    }
+    out
+}
+
+fn render_one(out: &mut String, e: &AocError, file: &str, content: &str, width: usize) {
+    let (line, msg, span, expected) = match e {
+        AocError::Parse {
+            line,
+            msg,
+            span,
+            expected,
+        } => (*line, msg, *span, expected),
+        e => {
+            writeln!(out, "error: {}", e).unwrap();
+            return;
+        }
+    };
+    writeln!(out, "error: {}", msg).unwrap();
+    let pad = " ".repeat(width);
+    let raw = match source_line(content, line) {
+        Some(raw) => raw,
+        None => {
+            writeln!(out, "{}--> {}", pad, file).unwrap();
+            return;
+        }
+    };
+    let (column, len) = underline(raw, span);
+    writeln!(out, "{}--> {}:{}:{}", pad, file, line, column + 1).unwrap();
+    writeln!(out, "{} |", pad).unwrap();
+    writeln!(out, "{:>w$} | {}", line, raw.trim_end(), w = width).unwrap();
+    let carets = format!("{}{}", " ".repeat(column), "^".repeat(len));
+    match expected {
+        Some(what) => writeln!(out, "{} | {} expected {}", pad, carets, what).unwrap(),
+        None => writeln!(out, "{} | {}", pad, carets).unwrap(),
+    }
+    out.push('\n');
+}
+
+/// Line `n` of `content`, untrimmed, numbered the way [`crate::util::numbered_lines`] does
+fn source_line(content: &str, n: usize) -> Option<&str> {
+    n.checked_sub(1).and_then(|i| content.lines().nth(i))
+}
+
+/// 0-based column and length in chars of `span` within `raw`. Spans are relative to the trimmed
+/// line; no span means the whole line. The length is at least 1, so that there's always a caret.
+fn underline(raw: &str, span: Option<Span>) -> (usize, usize) {
+    let indent = raw.len() - raw.trim_start().len();
+    let trimmed = raw.trim();
+    let span = span.unwrap_or(Span {
+        start: 0,
+        end: trimmed.len(),
+    });
+    let chars = |s: Option<&str>| s.map_or(0, |s| s.chars().count());
+    let column = chars(raw.get(..indent + span.start));
+    (column, chars(trimmed.get(span.start..span.end)).max(1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let content = "0,9 -> 5,9\n  0,9 -> 5,x\n";
+        let line = "0,9 -> 5,x";
+        let e = AocError::parse(2, "expected a number, found \"x\"")
+            .at(line, &line[9..])
+            .expected("a number");
+        assert_eq!(locate(&e, content), Some((2, 12)));
+        assert_eq!(
+            render(&e, "inputs/2021/d5", content),
+            "error: expected a number, found \"x\"\n \
+             --> inputs/2021/d5:2:12\n  \
+             |\n\
+             2 |   0,9 -> 5,x\n  \
+             |            ^ expected a number\n\n"
+        );
+
+        // several errors share a gutter wide enough for the longest line number
+        let content = format!("{}x-\n", "a-b\n".repeat(9));
+        let many = AocError::parse_many(vec![
+            AocError::parse(10, "expected `start-end`"),
+            AocError::parse(0, "no edges"),
+        ]);
+        let rendered = render(&many, "d12", &content);
+        assert!(rendered.starts_with("error: expected `start-end`\n  --> d12:10:1\n"));
+        assert!(rendered.contains("10 | x-\n   | ^^\n"));
+        assert!(rendered.contains("error: no edges\n  --> d12\n"));
+        assert!(rendered.ends_with("error: aborting due to 2 previous errors\n"));
+
+        let io = AocError::from(std::io::Error::other("gone"));
+        assert_eq!(render(&io, "d1", ""), "error: i/o error: gone\n");
+        assert_eq!(locate(&io, ""), None);
+    }
+}
@@ -0,0 +1,207 @@
+/// Crate-wide error type, returned by every parser and solver
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// Reading the input failed
+    Io(io::Error),
+    /// The input is malformed; `line` is 1-based, or 0 if the error isn't tied to a line. `span`
+    /// is the offending text within the line and `expected` says what should have been there.
+    Parse {
+        line: usize,
+        msg: String,
+        span: Option<Span>,
+        expected: Option<String>,
+    },
+    /// Several parse errors, in input order
+    ParseMany(Vec<AocError>),
+    /// A solver reached a state that well-formed input shouldn't produce
+    InvalidState(String),
+    /// The input is well-formed, but has no solution
+    Unsolvable(String),
+    /// Downloading puzzle input failed
+    Fetch(String),
+    /// Encoding an image failed
+    Render(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+/// Byte range `start..end` of a line of input, as it was given to the parser: trimmed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Span of `part` within `line`, which it must be a slice of. Falls back to the whole line.
+    pub fn of(line: &str, part: &str) -> Span {
+        let start = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        if start <= line.len() && part.len() <= line.len() - start {
+            Span {
+                start,
+                end: start + part.len(),
+            }
+        } else {
+            Span {
+                start: 0,
+                end: line.len(),
+            }
+        }
+    }
+}
+
+impl AocError {
+    pub fn parse<S: Into<String>>(line: usize, msg: S) -> Self {
+        AocError::Parse {
+            line,
+            msg: msg.into(),
+            span: None,
+            expected: None,
+        }
+    }
+
+    /// A single parse error for one error, or all of them for several
+    pub fn parse_many(mut errors: Vec<AocError>) -> Self {
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            AocError::ParseMany(errors)
+        }
+    }
+
+    /// Attach a line number to a parse error that doesn't have one yet
+    pub fn at_line(mut self, n: usize) -> Self {
+        if let AocError::Parse { line: line @ 0, .. } = &mut self {
+            *line = n;
+        }
+        self
+    }
+
+    /// Point a parse error at `part` of the line it's on, unless it already points somewhere
+    pub fn at(mut self, line: &str, part: &str) -> Self {
+        if let AocError::Parse { span: span @ None, .. } = &mut self {
+            *span = Some(Span::of(line, part));
+        }
+        self
+    }
+
+    /// Say what a parse error expected to find
+    pub fn expected<S: Into<String>>(mut self, what: S) -> Self {
+        if let AocError::Parse { expected, .. } = &mut self {
+            *expected = Some(what.into());
+        }
+        self
+    }
+
+    pub fn invalid_state<S: Into<String>>(msg: S) -> Self {
+        AocError::InvalidState(msg.into())
+    }
+
+    pub fn unsolvable<S: Into<String>>(msg: S) -> Self {
+        AocError::Unsolvable(msg.into())
+    }
+
+    pub fn fetch<S: Into<String>>(msg: S) -> Self {
+        AocError::Fetch(msg.into())
+    }
+
+    pub fn render<S: Into<String>>(msg: S) -> Self {
+        AocError::Render(msg.into())
+    }
+
+    /// Short machine-readable name for the kind of error
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AocError::Io(_) => "io",
+            AocError::Parse { .. } | AocError::ParseMany(_) => "parse",
+            AocError::InvalidState(_) => "invalid_state",
+            AocError::Unsolvable(_) => "unsolvable",
+            AocError::Fetch(_) => "fetch",
+            AocError::Render(_) => "render",
+        }
+    }
+
+    /// Input line the error refers to, if any
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AocError::Parse { line, .. } if *line > 0 => Some(*line),
+            AocError::ParseMany(errors) => errors.first().and_then(|e| e.line()),
+            _ => None,
+        }
+    }
+
+    /// The individual errors: each of several parse errors, or just this one
+    pub fn errors(&self) -> &[AocError] {
+        match self {
+            AocError::ParseMany(errors) => errors,
+            e => std::slice::from_ref(e),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "i/o error: {}", e),
+            AocError::Parse { line: 0, msg, .. } => write!(f, "parse error: {}", msg),
+            AocError::Parse { line, msg, .. } => {
+                write!(f, "parse error on line {}: {}", line, msg)
+            }
+            AocError::ParseMany(errors) => match errors.split_first() {
+                Some((first, rest)) => write!(f, "{} (and {} more)", first, rest.len()),
+                None => write!(f, "parse error"),
+            },
+            AocError::InvalidState(msg) => write!(f, "invalid puzzle state: {}", msg),
+            AocError::Unsolvable(msg) => write!(f, "unsolvable input: {}", msg),
+            AocError::Fetch(msg) => write!(f, "fetch error: {}", msg),
+            AocError::Render(msg) => write!(f, "render error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            AocError::parse(3, "expected a number").to_string(),
+            "parse error on line 3: expected a number"
+        );
+        assert_eq!(AocError::parse(0, "empty input").to_string(), "parse error: empty input");
+        let e: AocError = io::Error::new(io::ErrorKind::NotFound, "no such file").into();
+        assert_eq!(e.to_string(), "i/o error: no such file");
+        let many = AocError::parse_many(vec![AocError::parse(2, "a"), AocError::parse(5, "b")]);
+        assert_eq!(many.to_string(), "parse error on line 2: a (and 1 more)");
+        assert_eq!((many.kind(), many.line(), many.errors().len()), ("parse", Some(2), 2));
+    }
+
+    #[test]
+    fn test_span() {
+        let line = "0,9 -> 5,x";
+        assert_eq!(Span::of(line, &line[9..]), Span { start: 9, end: 10 });
+        let elsewhere = String::from("x");
+        assert_eq!(Span::of(line, &elsewhere), Span { start: 0, end: 10 });
+        let e = AocError::parse(1, "bad").at(line, &line[4..6]).at(line, line);
+        assert!(matches!(e, AocError::Parse { span: Some(Span { start: 4, end: 6 }), .. }));
+    }
+}
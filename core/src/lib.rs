@@ -0,0 +1,362 @@
+//! The parts of Advent of Code solutions that don't change from one December to the next: the
+//! error and answer types, input sources, parsing helpers, grids, and the [`Solution`] trait each
+//! year's days implement.
+//!
+//! [`Solution`]: solution::Solution
+pub mod answer;
+pub mod diagnostic;
+pub mod error;
+#[cfg(feature = "python")]
+mod python;
+pub mod solution;
+pub mod source;
+
+pub mod util {
+    use std::fs::File;
+    use std::io::{self, BufRead, Read};
+    use std::str::FromStr;
+
+    use crate::error::{AocError, Result};
+
+    /// Return an iterator over lines in a txt file.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use aoc_core::util::read_lines;
+    /// for line in read_lines("inputs/2021/d1").unwrap() {
+    ///     if let Ok(ln) = line {
+    ///          println!("{}", ln);
+    ///     }
+    /// }
+    /// ```
+    pub fn read_lines(path: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
+        let in_file = File::open(path)?;
+        Ok(io::BufReader::new(in_file).lines())
+    }
+
+    /// Read entire file path into a String
+    pub fn read_to_string(path: &str) -> io::Result<String> {
+        read_from(File::open(path)?)
+    }
+
+    /// Read everything from `reader` into a String
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<String> {
+        let mut result = String::new();
+        reader.read_to_string(&mut result)?;
+        Ok(result)
+    }
+
+    /// Read all of stdin into a String
+    pub fn read_stdin() -> io::Result<String> {
+        read_from(io::stdin().lock())
+    }
+
+    /// Call `f` on each non-blank line read from `reader`, trimmed, along with its 1-based line
+    /// number. The streaming counterpart of [`numbered_lines`]: lines are read one at a time into
+    /// the same buffer, so memory use doesn't grow with the input. Stops at the first error.
+    pub fn for_each_line<R, F>(mut reader: R, mut f: F) -> Result<()>
+    where
+        R: BufRead,
+        F: FnMut(usize, &str) -> Result<()>,
+    {
+        let mut buf = String::new();
+        let mut n = 0;
+        loop {
+            buf.clear();
+            if reader.read_line(&mut buf)? == 0 {
+                return Ok(());
+            }
+            n += 1;
+            let line = buf.trim();
+            if !line.is_empty() {
+                f(n, line)?;
+            }
+        }
+    }
+
+    /// Iterate over the non-blank lines of `content`, trimmed, along with their 1-based line
+    /// numbers
+    pub fn numbered_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty())
+    }
+
+    /// Parse each non-blank line of `content` with `f`, like [`numbered_lines`], but carry on
+    /// past lines that fail to parse so that all their errors are reported together
+    pub fn parse_lines<T, F>(content: &str, mut f: F) -> Result<Vec<T>>
+    where
+        F: FnMut(usize, &str) -> Result<T>,
+    {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for (n, line) in numbered_lines(content) {
+            match f(n, line) {
+                Ok(v) => values.push(v),
+                Err(e @ AocError::Parse { .. }) => errors.push(e),
+                Err(e) => return Err(e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(AocError::parse_many(errors))
+        }
+    }
+
+    /// Parse a single number, reporting `line` on failure
+    pub fn parse_num<T: FromStr>(s: &str, line: usize) -> Result<T> {
+        s.trim().parse::<T>().map_err(|_| {
+            AocError::parse(line, format!("expected a number, found {:?}", s)).expected("a number")
+        })
+    }
+
+    /// Parse `field`, a slice of `line`, as a number, pointing at the field on failure
+    pub fn parse_field<T: FromStr>(line: &str, field: &str, n: usize) -> Result<T> {
+        parse_num(field, n).map_err(|e| e.at(line, field))
+    }
+
+    pub fn read_ints(path: &str) -> Result<Vec<i64>> {
+        parse_ints(&read_to_string(path)?)
+    }
+
+    /// Parse one integer per non-blank line
+    pub fn parse_ints(content: &str) -> Result<Vec<i64>> {
+        parse_lines(content, |n, line| parse_num::<i64>(line, n))
+    }
+}
+
+pub mod grid {
+    use std::fmt;
+
+    use itertools::iproduct;
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct Grid<T> {
+        pub rows: usize,
+        pub cols: usize,
+        content: Vec<T>,
+    }
+
+    impl<T> Grid<T> {
+        pub fn new(rows: usize, cols: usize, fill: T) -> Self
+        where
+            T: Clone,
+        {
+            let mut content = Vec::new();
+            content.resize(rows * cols, fill);
+            Grid {
+                rows,
+                cols,
+                content,
+            }
+        }
+
+        pub fn from_vec(rows: usize, cols: usize, content: Vec<T>) -> Result<Self, &'static str> {
+            if content.len() == rows * cols {
+                Ok(Grid {
+                    rows,
+                    cols,
+                    content,
+                })
+            } else {
+                Err("rows * cols vs. content length mismatch")
+            }
+        }
+
+        pub fn from_rows(content: Vec<Vec<T>>) -> Result<Self, &'static str> {
+            if content.is_empty() {
+                return Ok(Grid {
+                    rows: 0,
+                    cols: 0,
+                    content: Vec::new(),
+                });
+            }
+            let rows = content.len();
+            let cols = content[0].len();
+            if content.iter().any(|row| row.len() != cols) {
+                return Err("rows have different lengths");
+            }
+            Ok(Grid {
+                rows,
+                cols,
+                content: content.into_iter().flatten().collect(),
+            })
+        }
+
+        /// The grid as a vector of rows, the inverse of `from_rows`
+        pub fn into_rows(self) -> Vec<Vec<T>> {
+            let mut content = self.content.into_iter();
+            (0..self.rows)
+                .map(|_| content.by_ref().take(self.cols).collect())
+                .collect()
+        }
+
+        /// Get element from the grid at (row, col)
+        pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+            if row < self.rows && col < self.cols {
+                Some(&self.content[row * self.cols + col])
+            } else {
+                None
+            }
+        }
+
+        /// Get element from the grid at (row, col)
+        /// variant that accepts positive and negative row/col
+        pub fn geti32(&self, row: i32, col: i32) -> Option<&T> {
+            if 0 <= row && row < (self.rows as i32) && 0 <= col && col < (self.cols as i32) {
+                Some(&self.content[(row as usize) * self.cols + (col as usize)])
+            } else {
+                None
+            }
+        }
+
+        /// Set element on the grid at (row, col)
+        pub fn set(&mut self, row: usize, col: usize, value: T) {
+            if row < self.rows && col < self.cols {
+                self.content[row * self.cols + col] = value;
+            } else {
+                panic!(
+                    "({}, {}) out of bounds for grid size {}x{}",
+                    row, col, self.rows, self.cols
+                );
+            }
+        }
+
+        /// Set element on the grid at (row, col)
+        /// variant that accepts positive and negative row/col
+        pub fn seti32(&mut self, row: i32, col: i32, value: T) {
+            if 0 <= row && row < (self.rows as i32) && 0 <= col && col < (self.cols as i32) {
+                self.content[(row as usize) * self.cols + (col as usize)] = value;
+            } else {
+                panic!(
+                    "({}, {}) out of bounds for grid size {}x{}",
+                    row, col, self.rows, self.cols
+                );
+            }
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item=&T> {
+            self.content.iter()
+        }
+
+        /// Iterate over position tuples (row, col) in the grid
+        pub fn iter_pos(&self) -> impl Iterator<Item=(usize, usize)> {
+            iproduct!(0..self.rows, 0..self.cols)
+        }
+
+        /// Iterate over tuples (row, col, value) in the grid
+        pub fn iter_pos_val(&self) -> impl Iterator<Item=(usize, usize, &T)> {
+            self.iter_pos().map(|(r, c)| (r, c, self.get(r, c).unwrap()))
+        }
+
+        pub fn iter_mut(&mut self) -> impl Iterator<Item=&mut T> {
+            self.content.iter_mut()
+        }
+
+        pub fn fmt_with_conversion<F>(&self, converter: F) -> String
+            where F: Fn(&T) -> String,
+        {
+            let mut result = String::new();
+            for y in 0..self.rows {
+                for x in 0..self.cols {
+                    let value = self.get(y, x).unwrap();
+                    result += &converter(value)
+                }
+                result += "\n";
+            }
+            result
+        }
+    }
+
+    // render grids on the terminal, for fun and laughs
+    // TODO: don't assume each T renders at the same width
+    impl<T: std::fmt::Display> fmt::Display for Grid<T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let mut result = String::new();
+            for y in 0..self.rows {
+                for x in 0..self.cols {
+                    let value = self.get(y, x).unwrap();
+                    result += &format!("{}", value)
+                }
+                result += "\n";
+            }
+            writeln!(f, "{}", result)
+        }
+    }
+
+    #[cfg(test)]
+    mod test_util {
+        use super::*;
+
+        #[test]
+        fn test_for_each_line() {
+            use crate::error::AocError;
+            use crate::util;
+
+            let mut lines = Vec::new();
+            util::for_each_line(" a \n\n b\n".as_bytes(), |n, l| {
+                lines.push((n, l.to_string()));
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(lines, vec![(1, "a".to_string()), (3, "b".to_string())]);
+
+            let stop = util::for_each_line("a\nb\n".as_bytes(), |n, _| Err(AocError::parse(n, "")));
+            assert!(matches!(stop, Err(AocError::Parse { line: 1, .. })));
+        }
+
+        #[test]
+        fn test_parse_lines() {
+            use crate::error::AocError;
+            use crate::util;
+
+            assert_eq!(util::parse_ints("1\n\n2\n").unwrap(), vec![1, 2]);
+            let errors = util::parse_ints("x\n2\ny\n").unwrap_err();
+            let lines: Vec<_> = errors.errors().iter().map(|e| e.line()).collect();
+            assert_eq!(lines, vec![Some(1), Some(3)]);
+
+            let line = "1,x";
+            let e = util::parse_field::<u32>(line, &line[2..], 4).unwrap_err();
+            let msg = "parse error on line 4: expected a number, found \"x\"";
+            assert_eq!(e.to_string(), msg);
+            assert!(matches!(e, AocError::Parse { span: Some(s), .. } if s.start == 2));
+        }
+
+        #[test]
+        fn test_grid_api() {
+            let mut grid = Grid::new(5, 5, false);
+            assert_eq!(grid.get(0, 0), Some(&false));
+            assert_eq!(grid.get(0, 501), None);
+            assert_eq!(grid.get(501, 0), None);
+            grid.set(1, 1, true);  // does not panic
+            assert_eq!(grid.get(1, 1), Some(&true));
+        }
+
+        #[test]
+        #[should_panic(expected = "(100, 100) out of bounds for grid size 5x5")]
+        fn test_grid_set_panic() {
+            let mut grid = Grid::new(5, 5, false);
+            grid.set(100, 100, true);
+        }
+
+        #[test]
+        fn test_from_rows() {
+            let content = vec![vec![0, 1, 0], vec![2, 3, 4]];
+            let grid = Grid::from_rows(content.clone()).unwrap();
+            assert_eq!(grid.get(0, 0), Some(&0));
+            assert_eq!(grid.into_rows(), content);
+
+            let ragged = vec![vec![0, 1, 0], vec![2, 3]];
+            assert!(Grid::from_rows(ragged).is_err());
+        }
+
+        #[test]
+        fn test_display_grid() {
+            let content = vec![vec![0, 1, 0], vec![2, 3, 4]];
+            println!("{}", Grid::from_rows(content).unwrap());
+        }
+    }
+}
@@ -0,0 +1,33 @@
+/// Conversions to and from Python for the core types, so that any year's Python bindings can take
+/// and return grids and raise errors as the matching Python exceptions: parse errors as
+/// `ValueError`, I/O errors as `OSError` and anything else as `RuntimeError`.
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::error::AocError;
+use crate::grid::Grid;
+
+impl From<AocError> for PyErr {
+    fn from(e: AocError) -> PyErr {
+        match e {
+            AocError::Io(e) => e.into(),
+            AocError::Parse { .. } | AocError::ParseMany(_) => PyValueError::new_err(e.to_string()),
+            e => PyRuntimeError::new_err(e.to_string()),
+        }
+    }
+}
+
+/// Grids come from lists of rows, which must all be the same length
+impl<'py, T: FromPyObject<'py>> FromPyObject<'py> for Grid<T> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let rows: Vec<Vec<T>> = ob.extract()?;
+        Grid::from_rows(rows).map_err(PyValueError::new_err)
+    }
+}
+
+/// Grids become lists of rows
+impl<T: IntoPy<PyObject>> IntoPy<PyObject> for Grid<T> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.into_rows().into_py(py)
+    }
+}
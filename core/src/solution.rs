@@ -0,0 +1,255 @@
+/// Uniform interface over each day's solution, whatever its year.
+///
+/// Each day implements [`Solution`] on a unit struct, e.g. `d5::Day5`. Since `Solution` has
+/// associated types it can't be used as a trait object directly, so registries store each day
+/// behind the object safe [`Day`] trait instead, wrapped in [`Registered`].
+use std::fmt;
+use std::io::{BufRead, Read};
+use std::marker::PhantomData;
+
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::source::Source;
+use crate::util;
+
+/// One of the two parts of a puzzle
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    /// Convert a part number, 1 or 2, into a Part
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The first Advent of Code. Command lines tell years from days by this: any number from here on
+/// is a year.
+pub const FIRST_YEAR: u32 = 2015;
+
+/// Name of a day's own implementation of each part, as opposed to its [`Variant`]s
+pub const MAIN: &str = "main";
+
+/// A named alternative implementation of one part of a day, to be cross-checked and benchmarked
+/// against the day's own
+pub struct Variant<I> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&I) -> Result<Answer>,
+}
+
+/// A day's solution: parse the input once, then solve each part on the parsed input
+pub trait Solution {
+    /// Puzzle year and day number
+    const YEAR: u32;
+    const DAY: u32;
+
+    /// Parsed puzzle input, shared by both parts
+    type Input;
+    /// Each part's answer, in whatever type suits the day
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    /// Parse puzzle input from its text
+    fn parse(content: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    fn parse_reader<R: Read>(reader: R) -> Result<Self::Input> {
+        Self::parse(&util::read_from(reader)?)
+    }
+
+    fn parse_stdin() -> Result<Self::Input> {
+        Self::parse(&util::read_stdin()?)
+    }
+
+    fn parse_file(input_file: &str) -> Result<Self::Input> {
+        Self::parse(&util::read_to_string(input_file)?)
+    }
+
+    /// Solve `part` straight from `reader`, a line at a time, without parsing the whole input
+    /// first. Days that can do this in constant memory override it; the rest return None.
+    fn stream<R: BufRead>(_part: Part, _reader: R) -> Option<Result<Answer>> {
+        None
+    }
+
+    /// Alternative implementations of either part, which should agree with `part1` and `part2`
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+}
+
+/// Object safe view of a [`Solution`], used by the registry
+pub trait Day: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+
+    /// Parse puzzle input text, ready to be solved
+    fn parse(&self, content: &str) -> Result<Box<dyn Parsed>>;
+
+    /// Solve `part` straight from `reader`, if the day supports streaming that part
+    fn stream(&self, _part: Part, _reader: &mut dyn BufRead) -> Option<Result<Answer>> {
+        None
+    }
+
+    /// Parts and names of the day's [`Variant`]s
+    fn variants(&self) -> Vec<(Part, &'static str)> {
+        Vec::new()
+    }
+
+    /// Read and parse input from a file, stdin or an in-memory string
+    fn parse_source(&self, source: &Source) -> Result<Box<dyn Parsed>> {
+        self.parse(&source.read()?)
+    }
+
+    /// Default input file for the day, `inputs/<YEAR>/d<DAY>`
+    fn default_input(&self) -> Source {
+        Source::File(format!("inputs/{}/d{}", self.year(), self.day()).into())
+    }
+
+    /// Input file holding the puzzle's worked example
+    fn test_input(&self) -> Source {
+        Source::File(format!("inputs/{}/d{}_test", self.year(), self.day()).into())
+    }
+}
+
+/// Parsed input for a day, which can solve either part
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<Answer>;
+
+    /// Solve `part` with the variant called `name`, or with the day's own implementation for
+    /// [`MAIN`]. `None` if the day has no such variant.
+    fn solve_variant(&self, _part: Part, _name: &str) -> Option<Result<Answer>> {
+        None
+    }
+}
+
+/// Adapter from a [`Solution`] to the object safe [`Day`], for registries:
+/// `&Registered::<d5::Day5>::new()` is a `&'static dyn Day`
+pub struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Registered<S> {
+    pub const fn new() -> Self {
+        Registered(PhantomData)
+    }
+}
+
+impl<S> Default for Registered<S> {
+    fn default() -> Self {
+        Registered::new()
+    }
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => S::part1(&self.0).map(Into::into),
+            Part::Two => S::part2(&self.0).map(Into::into),
+        }
+    }
+
+    fn solve_variant(&self, part: Part, name: &str) -> Option<Result<Answer>> {
+        if name == MAIN {
+            return Some(self.solve(part));
+        }
+        let variant = S::variants()
+            .into_iter()
+            .find(|v| v.part == part && v.name == name)?;
+        Some((variant.solve)(&self.0))
+    }
+}
+
+impl<S: Solution + 'static> Day for Registered<S> {
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse(&self, content: &str) -> Result<Box<dyn Parsed>> {
+        Ok(Box::new(ParsedInput::<S>(S::parse(content)?)))
+    }
+
+    fn stream(&self, part: Part, reader: &mut dyn BufRead) -> Option<Result<Answer>> {
+        S::stream(part, reader)
+    }
+
+    fn variants(&self) -> Vec<(Part, &'static str)> {
+        S::variants().into_iter().map(|v| (v.part, v.name)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Sums the numbers on its input's lines, or multiplies them
+    struct Sums;
+
+    impl Solution for Sums {
+        const YEAR: u32 = 2015;
+        const DAY: u32 = 25;
+        type Input = Vec<i64>;
+        type Answer1 = i64;
+        type Answer2 = i64;
+
+        fn parse(content: &str) -> Result<Vec<i64>> {
+            util::parse_ints(content)
+        }
+
+        fn part1(input: &Vec<i64>) -> Result<i64> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Vec<i64>) -> Result<i64> {
+            Ok(input.iter().product())
+        }
+
+        fn variants() -> Vec<Variant<Vec<i64>>> {
+            let reversed = |input: &Vec<i64>| Ok(input.iter().rev().sum::<i64>().into());
+            vec![Variant { name: "reversed", part: Part::One, solve: reversed }]
+        }
+    }
+
+    #[test]
+    fn test_registered() {
+        let day: &dyn Day = &Registered::<Sums>::new();
+        assert_eq!((day.year(), day.day()), (2015, 25));
+        assert_eq!(day.default_input().to_string(), "inputs/2015/d25");
+        assert_eq!(day.test_input().to_string(), "inputs/2015/d25_test");
+        assert_eq!(day.variants(), vec![(Part::One, "reversed")]);
+
+        let parsed = day.parse("2\n3\n4\n").unwrap();
+        assert_eq!(parsed.solve(Part::Two).unwrap(), Answer::Int(24));
+        assert_eq!(parsed.solve_variant(Part::One, MAIN).unwrap().unwrap(), Answer::Int(9));
+        assert_eq!(parsed.solve_variant(Part::One, "reversed").unwrap().unwrap(), Answer::Int(9));
+        assert!(parsed.solve_variant(Part::Two, "reversed").is_none());
+        assert!(day.stream(Part::One, &mut "1\n".as_bytes()).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+/// Where puzzle input comes from: a file, stdin, or an in-memory string
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+
+use crate::error::Result;
+use crate::util;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl Source {
+    /// Interpret a command line argument: `-` means stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Read the whole input into a String
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::File(path) => Ok(util::read_from(File::open(path)?)?),
+            Source::Stdin => Ok(util::read_stdin()?),
+            Source::Text(text) => Ok(text.clone()),
+        }
+    }
+
+    /// Open the input for reading a line at a time, without holding all of it in memory
+    pub fn reader(&self) -> Result<Box<dyn BufRead + '_>> {
+        match self {
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::Text(text) => Ok(Box::new(text.as_bytes())),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Text(_) => write!(f, "<string>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(Source::from_arg("inputs/2021/d1").to_string(), "inputs/2021/d1");
+
+        let text = Source::Text("1\n2\n".to_string());
+        assert_eq!(text.read().unwrap(), "1\n2\n");
+        assert_eq!(text.reader().unwrap().lines().count(), 2);
+        assert_eq!(text.to_string(), "<string>");
+        assert!(Source::from_arg("inputs/no_such_file").read().is_err());
+        assert!(Source::from_arg("inputs/no_such_file").reader().is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Fuzz every day's parser: no input may make a parser panic.
+//!
+//! The first byte selects the day, the rest is the puzzle input. Run with
+//! `cargo +nightly fuzz run parse`.
+#![no_main]
+use aoc_2021::solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((&selector, rest)) = data.split_first() {
+        let days: Vec<_> = solution::days().collect();
+        let day = days[selector as usize % days.len()];
+        if let Ok(content) = std::str::from_utf8(rest) {
+            let _ = day.parse(content);
+        }
+    }
+});
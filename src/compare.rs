@@ -0,0 +1,115 @@
+/// Cross-checking and benchmarking of each day's alternative implementations, its
+/// [`Variant`](crate::solution::Variant)s, against its own.
+///
+/// Every variant of a part is run on the same parsed input as the day's own implementation of that
+/// part, and must come up with the same answer, or fail as well.
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::gen;
+use crate::report::ErrorDetail;
+use crate::solution::{Day, Part, MAIN};
+use crate::timing::{self, Stats};
+
+/// One implementation of one part, run on one input
+#[derive(Debug)]
+pub struct Comparison {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    /// [`MAIN`] for the day's own implementation
+    pub variant: &'static str,
+    pub answer: std::result::Result<Answer, ErrorDetail>,
+    /// Whether the answer, or failure, is the same as the day's own implementation's
+    pub agrees: bool,
+    pub stats: Stats,
+}
+
+/// Inputs to cross-check `day` on, as (name, content) pairs: its example and puzzle inputs, where
+/// they exist, then `generated` random inputs of the usual size
+pub fn inputs(day: &dyn Day, generated: u64) -> Vec<(String, String)> {
+    let mut inputs: Vec<(String, String)> = [day.test_input(), day.default_input()]
+        .iter()
+        .filter_map(|source| Some((source.to_string(), source.read().ok()?)))
+        .collect();
+    if let Some(size) = gen::default_size(day.year(), day.day()) {
+        for seed in 0..generated {
+            if let Some(content) = gen::generate(day.year(), day.day(), size, seed) {
+                inputs.push((format!("generated, seed {}", seed), content));
+            }
+        }
+    }
+    inputs
+}
+
+/// Run every part of `day` that has variants on `content`, `runs` times each, with the day's own
+/// implementation first. Fails only if the input can't be parsed.
+pub fn compare(day: &dyn Day, input: &str, content: &str, runs: usize) -> Result<Vec<Comparison>> {
+    let variants = day.variants();
+    if variants.is_empty() {
+        return Ok(Vec::new());
+    }
+    let parsed = day.parse(content)?;
+    let mut comparisons = Vec::new();
+    for part in Part::ALL {
+        let names: Vec<&str> = variants
+            .iter()
+            .filter(|(p, _)| *p == part)
+            .map(|(_, name)| *name)
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+        let mut main = None;
+        for name in std::iter::once(MAIN).chain(names) {
+            let (result, stats) = timing::repeat(runs, || {
+                Ok(parsed.solve_variant(part, name).expect("registered variant"))
+            })?;
+            let answer = result.map_err(|e| ErrorDetail::from(&e));
+            let main_answer = main.get_or_insert_with(|| answer.clone());
+            let agrees = match (&answer, main_answer) {
+                (Ok(a), Ok(b)) => a == b,
+                (Err(_), Err(_)) => true,
+                _ => false,
+            };
+            comparisons.push(Comparison {
+                year: day.year(),
+                day: day.day(),
+                part,
+                input: input.to_string(),
+                variant: name,
+                answer,
+                agrees,
+                stats,
+            });
+        }
+    }
+    Ok(comparisons)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution;
+
+    #[test]
+    fn test_compare() {
+        for day in [3, 6] {
+            let day = solution::get(2021, day).unwrap();
+            for (input, content) in inputs(day, 2) {
+                for c in compare(day, &input, &content, 1).unwrap() {
+                    assert!(c.agrees, "day {} part {} {} on {}", c.day, c.part, c.variant, input);
+                    assert!(c.answer.is_ok());
+                }
+            }
+        }
+
+        let day = solution::get(2021, 3).unwrap();
+        let comparisons = compare(day, "example", "00100\n11110\n10110\n", 2).unwrap();
+        let names: Vec<_> = comparisons.iter().map(|c| (c.part, c.variant)).collect();
+        assert_eq!(names, vec![(Part::Two, MAIN), (Part::Two, "alt2")]);
+        assert!(comparisons.iter().all(|c| c.stats.runs == 2));
+        assert_eq!(inputs(day, 3).len(), 5);
+        assert!(compare(solution::get(2021, 1).unwrap(), "x", "x", 1).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+/// Example fixtures extracted from saved puzzle pages, without going online.
+///
+/// A puzzle page has one `<article class="day-desc">` per part. Example inputs are the
+/// `<pre><code>` blocks, and each part's example answer is, by convention, the last emphasized
+/// `<code>` outside of them, as in `<code><em>7</em></code>` or
+/// `<code>188 * 24 = <em>4512</em></code>`. Not every block is an input (some show intermediate
+/// states), so [`Page::blocks`] lists them all and the caller picks which become fixtures.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::error::{AocError, Result};
+use crate::manifest::Manifest;
+use crate::util;
+
+#[derive(Debug)]
+pub struct Page {
+    pub year: u32,
+    pub day: u32,
+    /// Text of every `<pre><code>` block, in page order
+    pub blocks: Vec<String>,
+    /// Example answer for each part on the page
+    pub answers: [Option<String>; 2],
+}
+
+/// A fixture file to write, and the answers for it if known
+#[derive(Debug, Eq, PartialEq)]
+pub struct Fixture {
+    pub year: u32,
+    pub day: u32,
+    pub path: PathBuf,
+    pub content: String,
+    pub answers: [Option<String>; 2],
+}
+
+impl Page {
+    pub fn load(path: &str) -> Result<Page> {
+        Page::parse(&util::read_to_string(path)?)
+    }
+
+    pub fn parse(html: &str) -> Result<Page> {
+        let heading = Regex::new(r"--- Day (\d+):").unwrap();
+        let day = heading
+            .captures(html)
+            .ok_or_else(|| AocError::parse(0, "no `--- Day N: ...` heading, not a puzzle page?"))?;
+        let day = util::parse_num::<u32>(&day[1], 0)?;
+        let title = Regex::new(r"<title>.*Advent of Code (\d+)</title>").unwrap();
+        let year = title.captures(html).ok_or_else(|| {
+            AocError::parse(0, "no `Advent of Code YYYY` title, can't tell the puzzle's year")
+        })?;
+        let year = util::parse_num::<u32>(&year[1], 0)?;
+        let article = Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+        let pre = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+        let code = Regex::new(r"(?s)<code>(.*?)</code>").unwrap();
+        let em = Regex::new(r"(?s)<em>(.*?)</em>").unwrap();
+        let mut blocks = Vec::new();
+        let mut answers = [None, None];
+        for (i, article) in article.captures_iter(html).take(2).enumerate() {
+            let article = &article[1];
+            blocks.extend(pre.captures_iter(article).map(|c| text(&c[1])));
+            answers[i] = code
+                .captures_iter(&pre.replace_all(article, ""))
+                .filter_map(|c| em.captures(&c[1]).map(|e| text(&e[1])))
+                .last();
+        }
+        Ok(Page {
+            year,
+            day,
+            blocks,
+            answers,
+        })
+    }
+
+    /// Fixtures in the page's year's directory in `dir` for the chosen blocks:
+    /// `<YEAR>/d<DAY>_test`, then `<YEAR>/d<DAY>_test2` and so on. Only the first gets the page's
+    /// answers, since there's no telling which example the later ones belong to. Returns an error
+    /// if a block doesn't exist.
+    pub fn fixtures(&self, dir: &Path, blocks: &[usize]) -> Result<Vec<Fixture>> {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                let content = self.blocks.get(b).ok_or_else(|| {
+                    let msg = format!("page has {} blocks, no block {}", self.blocks.len(), b);
+                    AocError::parse(0, msg)
+                })?;
+                let suffix = if i == 0 { String::new() } else { (i + 1).to_string() };
+                Ok(Fixture {
+                    year: self.year,
+                    day: self.day,
+                    path: dir
+                        .join(self.year.to_string())
+                        .join(format!("d{}_test{}", self.day, suffix)),
+                    content: content.clone(),
+                    answers: if i == 0 { self.answers.clone() } else { [None, None] },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Plain text of an HTML fragment: tags dropped, entities decoded
+fn text(html: &str) -> String {
+    let tag = Regex::new(r"<[^>]*>").unwrap();
+    tag.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// TOML manifest entry for a fixture. Answers that look like integers are written as integers.
+pub fn manifest_entry(fixture: &Fixture) -> String {
+    let mut entry = format!(
+        "[[answer]]\nyear = {}\nday = {}\ninput = {}\n",
+        fixture.year,
+        fixture.day,
+        toml::Value::String(fixture.path.display().to_string())
+    );
+    for (part, answer) in fixture.answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            let value = match answer.parse::<i64>() {
+                Ok(n) => toml::Value::Integer(n),
+                Err(_) => toml::Value::String(answer.clone()),
+            };
+            entry += &format!("part{} = {}\n", part + 1, value);
+        }
+    }
+    entry
+}
+
+/// Write fixtures, and append an entry to the manifest at `manifest_path` for each one with
+/// answers that doesn't have one yet. An existing fixture with different content is an error
+/// unless `force` is set.
+pub fn write(fixtures: &[Fixture], manifest_path: &Path, force: bool) -> Result<()> {
+    for fixture in fixtures {
+        match fs::read_to_string(&fixture.path) {
+            Ok(old) if old != fixture.content && !force => {
+                return Err(AocError::Io(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} exists with different content", fixture.path.display()),
+                )));
+            }
+            _ => {
+                if let Some(dir) = fixture.path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&fixture.path, &fixture.content)?;
+            }
+        }
+    }
+    let mut manifest = match fs::read_to_string(manifest_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let existing = Manifest::from_string(&manifest)?;
+    for fixture in fixtures.iter().filter(|f| f.answers.iter().any(Option::is_some)) {
+        let input = fixture.path.display().to_string();
+        if existing.entries_for(fixture.year, fixture.day).all(|e| e.input != input) {
+            if !manifest.is_empty() && !manifest.ends_with("\n\n") {
+                manifest.push('\n');
+            }
+            manifest += &manifest_entry(fixture);
+        }
+    }
+    fs::write(manifest_path, manifest)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    /// Trimmed down day 1 page, as saved by a browser after solving both parts
+    const PAGE: &str = r#"<html><head><title>Day 1 - Advent of Code 2021</title></head><body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Sonar Sweep ---</h2>
+<p>For example, suppose you had the following report:</p>
+<pre><code>199
+200
+208
+</code></pre>
+<p>This report indicates that, scanning outward from the submarine, ...</p>
+<pre><code>199 (N/A - no previous measurement)
+200 (<em>increased</em>)
+</code></pre>
+<p>In this example, there are <code><em>7</em></code> measurements that are larger than the
+previous measurement.</p>
+</article>
+<p>Your puzzle answer was <code>1451</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>A: 607 (N/A - &lt;no change&gt;)
+</code></pre>
+<p>In this example, the sum is <code>199 + 200 = <em>5</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let page = Page::parse(PAGE).unwrap();
+        assert_eq!((page.year, page.day), (2021, 1));
+        assert_eq!(page.blocks.len(), 3);
+        assert_eq!(page.blocks[0], "199\n200\n208\n");
+        assert_eq!(page.blocks[1], "199 (N/A - no previous measurement)\n200 (increased)\n");
+        assert_eq!(page.blocks[2], "A: 607 (N/A - <no change>)\n");
+        assert_eq!(page.answers, [Some("7".to_string()), Some("5".to_string())]);
+
+        assert!(matches!(Page::parse("<html></html>"), Err(AocError::Parse { .. })));
+        let untitled = PAGE.replace("Advent of Code 2021", "");
+        assert!(matches!(Page::parse(&untitled), Err(AocError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_write_fixtures() {
+        let dir = env::temp_dir().join(format!("aoc_2021_examples_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let manifest = dir.join("answers.toml");
+        let page = Page::parse(PAGE).unwrap();
+        assert!(page.fixtures(&dir, &[3]).is_err());
+        let fixtures = page.fixtures(&dir, &[0, 2]).unwrap();
+        assert_eq!(fixtures[1].path, dir.join("2021/d1_test2"));
+        assert_eq!(fixtures[1].answers, [None, None]);
+
+        // writing twice leaves one manifest entry
+        write(&fixtures, &manifest, false).unwrap();
+        write(&fixtures, &manifest, false).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("2021/d1_test")).unwrap(), "199\n200\n208\n");
+        let entries = Manifest::load(manifest.to_str().unwrap()).unwrap().entries;
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].year, entries[0].day), (2021, 1));
+        assert_eq!(entries[0].input, dir.join("2021/d1_test").display().to_string());
+
+        fs::write(dir.join("2021/d1_test"), "1\n").unwrap();
+        assert!(matches!(write(&fixtures, &manifest, false), Err(AocError::Io(_))));
+        write(&fixtures, &manifest, true).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
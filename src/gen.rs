@@ -0,0 +1,424 @@
+/// Seeded generators of random puzzle inputs, for stress tests and scaling studies.
+///
+/// Each generator takes a random number generator and a size, roughly the number of lines or
+/// items in the input, and returns an input that the day's parser accepts. Generators use ChaCha8
+/// so that the same seed gives the same input on every platform.
+///
+/// ```
+/// let input = aoc_2021::gen::generate(2021, 5, 100, 42).unwrap();
+/// assert_eq!(input.lines().count(), 100);
+/// ```
+use itertools::Itertools;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng as _, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub type Rng = ChaCha8Rng;
+
+/// Generate an input of `size` for `day`
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// (year, day, default size, generator)
+const GENERATORS: &[(u32, u32, usize, Generator)] = &[
+    (2021, 1, 2000, d1),
+    (2021, 2, 1000, d2),
+    (2021, 3, 1000, d3),
+    (2021, 4, 100, d4),
+    (2021, 5, 500, d5),
+    (2021, 6, 300, d6),
+    (2021, 7, 1000, d7),
+    (2021, 8, 200, d8),
+    (2021, 9, 100, d9),
+    (2021, 10, 100, d10),
+    (2021, 11, 10, d11),
+    (2021, 12, 8, d12),
+    (2021, 13, 800, d13),
+];
+
+pub fn rng(seed: u64) -> Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+fn find(year: u32, day: u32) -> Option<&'static (u32, u32, usize, Generator)> {
+    GENERATORS.iter().find(|g| (g.0, g.1) == (year, day))
+}
+
+/// The generator for `day` of `year`, if there is one
+pub fn generator(year: u32, day: u32) -> Option<Generator> {
+    find(year, day).map(|g| g.3)
+}
+
+/// Size of a generated input comparable to the real puzzle input for `day` of `year`
+pub fn default_size(year: u32, day: u32) -> Option<usize> {
+    find(year, day).map(|g| g.2)
+}
+
+/// Generate an input of `size` for `day` of `year` from `seed`, or `None` if there's no
+/// generator for it
+pub fn generate(year: u32, day: u32, size: usize, seed: u64) -> Option<String> {
+    generator(year, day).map(|g| g(&mut rng(seed), size))
+}
+
+fn lines<I: IntoIterator<Item = String>>(lines: I) -> String {
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+/// `size` sonar depths (at least 3), a random walk that trends deeper
+pub fn d1(rng: &mut Rng, size: usize) -> String {
+    let mut depth: i64 = rng.gen_range(100..200);
+    lines((0..size.max(3)).map(|_| {
+        depth = (depth + rng.gen_range(-20..=30)).max(0);
+        depth.to_string()
+    }))
+}
+
+/// `size` submarine commands (at least 1) that never take it above the surface
+pub fn d2(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    lines((0..size.max(1)).map(|_| {
+        let x = rng.gen_range(1..=9);
+        let command = match rng.gen_range(0..3) {
+            0 => "forward",
+            1 if depth >= x => "up",
+            _ => "down",
+        };
+        depth += match command {
+            "up" => -x,
+            "down" => x,
+            _ => 0,
+        };
+        format!("{} {}", command, x)
+    }))
+}
+
+/// `size` distinct bitstrings (at least 2), of the smallest width from 5 that fits them twice over.
+/// Draws again until the CO2 scrubber rating, which needs the lines to disagree at every step,
+/// can be found.
+pub fn d3(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let width = (5..63).find(|w| 1usize << w >= 2 * size).unwrap_or(63);
+    loop {
+        let input = bitstrings(rng, size, width);
+        let bvs = crate::y2021::d3::parse_input_from_string(&input).expect("valid bitstrings");
+        if crate::y2021::d3::d3_part2(&bvs).is_ok() {
+            return input;
+        }
+    }
+}
+
+/// `count` distinct bitstrings of `width` bits. Distinct values make sure the oxygen and CO2
+/// ratings narrow down to a single line. Panics if there aren't enough distinct values.
+pub fn bitstrings(rng: &mut Rng, count: usize, width: usize) -> String {
+    assert!(width < 64 && count <= 1 << width, "too few {}-bit values", width);
+    let values = index::sample(rng, 1 << width, count);
+    lines(values.into_iter().map(|v| format!("{:0width$b}", v, width = width)))
+}
+
+/// A bingo game with `size` boards (at least 1). Every number on every board gets called, so
+/// every board wins eventually.
+pub fn d4(rng: &mut Rng, size: usize) -> String {
+    let numbers = 100;
+    let mut calls: Vec<usize> = (0..numbers).collect();
+    calls.shuffle(rng);
+    let mut out = calls.iter().join(",") + "\n";
+    for _ in 0..size.max(1) {
+        let board = index::sample(rng, numbers, 25).into_vec();
+        out.push('\n');
+        for row in board.chunks(5) {
+            out += &row.iter().map(|n| format!("{:>2}", n)).join(" ");
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// `size` vent lines on a 1000x1000 floor, horizontal, vertical or at 45 degrees
+pub fn d5(rng: &mut Rng, size: usize) -> String {
+    let extent = 1000;
+    lines((0..size).map(|_| {
+        let (dx, dy) = *[(1, 0), (0, 1), (1, 1), (1, -1)].choose(rng).unwrap();
+        let (x0, y0): (i64, i64) = (rng.gen_range(0..extent), rng.gen_range(0..extent));
+        // longest line in this direction that stays on the floor
+        let room = |p: i64, d: i64| match d {
+            1 => extent - 1 - p,
+            -1 => p,
+            _ => i64::MAX,
+        };
+        let len = rng.gen_range(0..=room(x0, dx).min(room(y0, dy)).min(extent / 2));
+        format!("{},{} -> {},{}", x0, y0, x0 + dx * len, y0 + dy * len)
+    }))
+}
+
+/// `size` lanternfish timers (at least 1), from 1 to 5
+pub fn d6(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| rng.gen_range(1..=5)).join(",") + "\n"
+}
+
+/// `size` crab positions (at least 1), spread over twice as many positions
+pub fn d7(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size).map(|_| rng.gen_range(0..2 * size)).join(",") + "\n"
+}
+
+/// Segments lit for each digit 0-9 on an unscrambled display
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `size` notes, each from a display with its own random wiring
+pub fn d8(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let mut wiring: Vec<char> = ('a'..='g').collect();
+        wiring.shuffle(rng);
+        let mut patterns: Vec<usize> = (0..10).collect();
+        patterns.shuffle(rng);
+        let outputs: Vec<usize> = (0..4).map(|_| rng.gen_range(0..10)).collect();
+        let mut scramble = |digit: &usize| {
+            let mut wires: Vec<char> = DIGIT_SEGMENTS[*digit]
+                .chars()
+                .map(|s| wiring[s as usize - 'a' as usize])
+                .collect();
+            wires.shuffle(rng);
+            wires.into_iter().collect::<String>()
+        };
+        let patterns = patterns.iter().map(&mut scramble).join(" ");
+        let outputs = outputs.iter().map(&mut scramble).join(" ");
+        format!("{} | {}", patterns, outputs)
+    }))
+}
+
+/// A `size` by `size` heightmap (at least 10 by 10). Heights rise with distance from a few basin
+/// centers, and basins are walled off from each other with 9s, so every basin has one low point.
+pub fn d9(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(10) as i64;
+    let ncenters = (size * size / 50).max(3);
+    let mut centers: Vec<(i64, i64)> = Vec::new();
+    // keep centers apart so each one is a low point; give up on a crowded map after many tries
+    for _ in 0..100 * ncenters {
+        let c = (rng.gen_range(0..size), rng.gen_range(0..size));
+        if centers.iter().all(|o| (o.0 - c.0).abs() + (o.1 - c.1).abs() >= 4) {
+            centers.push(c);
+            if centers.len() as i64 == ncenters {
+                break;
+            }
+        }
+    }
+    lines((0..size).map(|row| {
+        (0..size)
+            .map(|col| {
+                let mut dists: Vec<i64> = centers
+                    .iter()
+                    .map(|c| (c.0 - row).abs() + (c.1 - col).abs())
+                    .collect();
+                dists.sort_unstable();
+                let height = if dists[1] - dists[0] <= 1 { 9 } else { dists[0].min(8) };
+                char::from(b'0' + height as u8)
+            })
+            .collect()
+    }))
+}
+
+const CHUNK_PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// `size` lines of chunks (at least 1), each either incomplete or corrupted. An odd number of
+/// them are incomplete, so there's a middle completion score.
+pub fn d10(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let incomplete = (size / 2) | 1;
+    let mut kinds: Vec<bool> = (0..size).map(|i| i < incomplete).collect();
+    kinds.shuffle(rng);
+    lines(kinds.into_iter().map(|is_incomplete| {
+        // a balanced line, with the closing chunk delimiters remembered
+        let mut line = Vec::new();
+        let mut stack = Vec::new();
+        let len = rng.gen_range(20..=60);
+        while line.len() < len || !stack.is_empty() {
+            if !stack.is_empty() && (line.len() >= len || rng.gen_bool(0.5)) {
+                line.push(stack.pop().unwrap());
+            } else {
+                let (open, close) = *CHUNK_PAIRS.choose(rng).unwrap();
+                line.push(open);
+                stack.push(close);
+            }
+        }
+        if is_incomplete {
+            // cut it short somewhere with chunks still open
+            let mut depth = 0;
+            let cuts: Vec<usize> = line
+                .iter()
+                .enumerate()
+                .filter_map(|(i, c)| {
+                    depth += if CHUNK_PAIRS.iter().any(|p| p.0 == *c) { 1 } else { -1 };
+                    (depth > 0).then(|| i + 1)
+                })
+                .collect();
+            line.truncate(*cuts.choose(rng).unwrap());
+        } else {
+            // close one chunk with the wrong delimiter
+            let closes: Vec<usize> = (0..line.len())
+                .filter(|&i| CHUNK_PAIRS.iter().any(|p| p.1 == line[i]))
+                .collect();
+            let i = *closes.choose(rng).unwrap();
+            let wrong: Vec<char> =
+                CHUNK_PAIRS.iter().map(|p| p.1).filter(|&c| c != line[i]).collect();
+            line[i] = *wrong.choose(rng).unwrap();
+        }
+        line.into_iter().collect()
+    }))
+}
+
+/// A `size` by `size` grid of octopus energy levels (at least 1 by 1) that synchronizes within
+/// 10 steps, built directly rather than by simulating. Octopods all at one level flash together,
+/// and an octopus below that level whose neighbors are all at it still flashes with them, as
+/// long as it's no further below than its number of neighbors. So every other octopus of every
+/// other row is lowered by up to that many, and the rest stay at the shared level.
+pub fn d11(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let shared: u32 = rng.gen_range(1..10);
+    lines((0..size).map(|row| {
+        (0..size)
+            .map(|col| {
+                if row % 2 == 1 || col % 2 == 1 {
+                    return shared;
+                }
+                let edges = [row == 0, row == size - 1, col == 0, col == size - 1];
+                let neighbors = match edges.iter().filter(|&&e| e).count() {
+                    0 => 8,
+                    1 => 5,
+                    2 => 3,
+                    // a single octopus, which flashes on its own
+                    _ => 9,
+                };
+                rng.gen_range(shared.saturating_sub(neighbors)..=shared)
+            })
+            .join("")
+    }))
+}
+
+/// Name of cave `i`: two or more lowercase letters for a small cave, uppercase for a big one
+fn cave_name(mut i: usize, big: bool) -> String {
+    let base = if big { b'A' } else { b'a' };
+    let mut name = Vec::new();
+    while name.len() < 2 || i > 0 {
+        name.push(base + (i % 26) as u8);
+        i /= 26;
+    }
+    name.into_iter().rev().map(char::from).collect()
+}
+
+/// A cave system with `size` small caves (at least 1) and a big cave for every four small ones.
+/// Big caves are never connected to each other, so the number of paths is finite; it still grows
+/// very quickly with size.
+pub fn d12(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let small: Vec<String> = (0..size).map(|i| cave_name(i, false)).collect();
+    let big: Vec<String> = (0..(size / 4).max(1)).map(|i| cave_name(i, true)).collect();
+    let mut edges: Vec<(String, String)> = Vec::new();
+    let mut connect = |a: &String, b: &String| {
+        if a != b && !edges.iter().any(|e| (&e.0, &e.1) == (a, b) || (&e.0, &e.1) == (b, a)) {
+            edges.push((a.clone(), b.clone()));
+        }
+    };
+    // every small cave hangs off an earlier cave, so the system is connected
+    for (i, cave) in small.iter().enumerate() {
+        let earlier = rng.gen_range(0..i + big.len());
+        let other = if earlier < big.len() { &big[earlier] } else { &small[earlier - big.len()] };
+        connect(other, cave);
+    }
+    for cave in &big {
+        for _ in 0..2 {
+            connect(cave, small.choose(rng).unwrap());
+        }
+    }
+    for _ in 0..size / 4 {
+        connect(small.choose(rng).unwrap(), small.choose(rng).unwrap());
+    }
+    let start = "start".to_string();
+    let end = "end".to_string();
+    connect(&start, small.choose(rng).unwrap());
+    connect(&start, big.choose(rng).unwrap());
+    connect(small.choose(rng).unwrap(), &end);
+    connect(big.choose(rng).unwrap(), &end);
+    edges.shuffle(rng);
+    lines(edges.into_iter().map(|(a, b)| format!("{}-{}", a, b)))
+}
+
+/// `size` distinct dots and the folds that bring them down onto a 40x6 code, like the puzzle's.
+/// Dots are placed on the code, then reflected across each fold line or not, working outwards.
+/// Each choice of a point on the code and of reflections lands on a different dot, so there are
+/// only 40 * 6 * 2^12 dots to draw from, and larger sizes give all of them.
+pub fn d13(rng: &mut Rng, size: usize) -> String {
+    let (mut cols, mut rows) = (40, 6);
+    // folds in the order they're applied, innermost last
+    let mut folds = Vec::new();
+    for i in 0..12 {
+        if i % 2 == 0 {
+            folds.push(('x', cols));
+            cols = 2 * cols + 1;
+        } else {
+            folds.push(('y', rows));
+            rows = 2 * rows + 1;
+        }
+    }
+    folds.reverse();
+    let code = 40 * 6;
+    let reachable = code << folds.len();
+    let dots = index::sample(rng, reachable, size.min(reachable)).into_iter().map(|i| {
+        let (mut x, mut y) = (i % 40, i / 40 % 6);
+        // one bit per fold, for whether the dot is reflected across it
+        let mut reflections = i / code;
+        for &(axis, at) in folds.iter().rev() {
+            if reflections % 2 == 1 {
+                match axis {
+                    'x' => x = 2 * at - x,
+                    _ => y = 2 * at - y,
+                }
+            }
+            reflections /= 2;
+        }
+        format!("{},{}", x, y)
+    });
+    let folds = folds.iter().map(|(axis, at)| format!("fold along {}={}", axis, at));
+    lines(dots) + "\n" + &lines(folds)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::solution::{self, Part};
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for (&(year, day, ..), size) in GENERATORS.iter().cartesian_product([1, 10]) {
+            for seed in 0..3 {
+                let input = generate(year, day, size, seed).unwrap();
+                let parsed = solution::get(year, day).unwrap().parse(&input);
+                let parsed = parsed.unwrap_or_else(|e| panic!("day {} seed {}: {}", day, seed, e));
+                for part in Part::ALL {
+                    if let Err(e) = parsed.solve(part) {
+                        panic!("day {} size {} seed {} part {}: {}", day, size, seed, part, e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(2021, 4, 5, 7), generate(2021, 4, 5, 7));
+        assert_ne!(generate(2021, 4, 5, 7), generate(2021, 4, 5, 8));
+        assert_eq!(generate(2021, 14, 5, 7), None);
+        assert_eq!(generate(2022, 4, 5, 7), None);
+        assert_eq!(generate(2021, 3, 20, 0).unwrap().lines().collect::<HashSet<_>>().len(), 20);
+        let octopods = generate(2021, 11, 21, 0).unwrap();
+        let octopods = crate::y2021::d11::parse_input_from_string(&octopods).unwrap();
+        assert!(crate::y2021::d11::d11_part2(&octopods).unwrap() <= 10);
+        let dots = generate(2021, 13, 2_000_000, 0).unwrap();
+        assert_eq!(dots.lines().take_while(|l| !l.is_empty()).count(), (40 * 6) << 12);
+        assert_eq!(cave_name(0, false), "aa");
+        assert_eq!(cave_name(27, true), "BB");
+        assert_eq!(cave_name(26 * 26, false), "baa");
+    }
+}
@@ -0,0 +1,258 @@
+/// Puzzle input manager: resolves inputs from a local cache directory, downloading missing ones.
+/// Inputs are cached by year, as `<YEAR>/d<DAY>`, the layout of the `inputs` directory.
+///
+/// Inputs never change once published, so a cached input is never fetched again. Downloads are
+/// throttled to at most one request per `min_interval`, across processes, using the time recorded
+/// in a stamp file in the cache directory. Requests identify this project in their User-Agent, as
+/// the puzzle authors ask.
+///
+/// Configuration comes from the environment in [`InputManager::from_env`]:
+///
+/// - `AOC_SESSION`: session cookie of a logged in user, required for downloading
+/// - `AOC_BASE_URL`: server to download from, defaults to `https://adventofcode.com`
+/// - `AOC_CACHE_DIR`: cache directory, defaults to `inputs`
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{AocError, Result};
+use crate::solution::FIRST_YEAR;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_CACHE_DIR: &str = "inputs";
+
+const USER_AGENT: &str =
+    "github.com/benjaminfjones/advent-of-code-2021 by benjaminfjones@gmail.com";
+const STAMP_FILE: &str = ".last_fetch";
+
+#[derive(Clone, Debug)]
+pub struct InputManager {
+    cache_dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+    min_interval: Duration,
+}
+
+impl InputManager {
+    pub fn new<P: Into<PathBuf>>(cache_dir: P) -> Self {
+        InputManager {
+            cache_dir: cache_dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            min_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Configure from the `AOC_*` environment variables
+    pub fn from_env() -> Self {
+        let cache_dir = env::var("AOC_CACHE_DIR").unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string());
+        let mut manager = InputManager::new(cache_dir);
+        if let Ok(url) = env::var("AOC_BASE_URL") {
+            manager = manager.with_base_url(url);
+        }
+        if let Ok(session) = env::var("AOC_SESSION") {
+            manager = manager.with_session(session);
+        }
+        manager
+    }
+
+    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session<S: Into<String>>(mut self, session: S) -> Self {
+        self.session = Some(session.into().trim().to_string());
+        self
+    }
+
+    /// Minimum time between two downloads
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Where the input for `day` of `year` is cached, whether or not it exists yet
+    pub fn cached_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir.join(year.to_string()).join(format!("d{}", day))
+    }
+
+    /// URL the input for `day` of `year` is downloaded from
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    /// Path to the input for `day` of `year`, downloading it into the cache first if it's missing
+    pub fn resolve(&self, year: u32, day: u32) -> Result<PathBuf> {
+        let path = self.cached_path(year, day);
+        if !path.exists() {
+            let content = self.fetch(year, day)?;
+            fs::create_dir_all(path.parent().unwrap_or(&self.cache_dir))?;
+            // write then rename, so an interrupted download never leaves a partial input behind
+            let partial = path.with_extension("partial");
+            fs::write(&partial, content)?;
+            fs::rename(&partial, &path)?;
+        }
+        Ok(path)
+    }
+
+    /// Download the input for `day` of `year`, bypassing the cache
+    pub fn fetch(&self, year: u32, day: u32) -> Result<String> {
+        if year < FIRST_YEAR || !(1..=25).contains(&day) {
+            return Err(AocError::fetch(format!("no puzzle for {} day {}", year, day)));
+        }
+        let session = self
+            .session
+            .as_ref()
+            .ok_or_else(|| AocError::fetch("no session token, set AOC_SESSION"))?;
+        self.throttle()?;
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        let url = self.url(year, day);
+        let response = agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code, _) => {
+                    AocError::fetch(format!("{} returned HTTP status {}", url, code))
+                }
+                ureq::Error::Transport(t) => AocError::fetch(t.to_string()),
+            })?;
+        Ok(response.into_string()?)
+    }
+
+    /// Wait until `min_interval` has passed since the last download, then record this one
+    fn throttle(&self) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let stamp = self.cache_dir.join(STAMP_FILE);
+        if let Some(since) = elapsed_since_stamp(&stamp) {
+            if since < self.min_interval {
+                thread::sleep(self.min_interval - since);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+        fs::write(&stamp, now.as_nanos().to_string())?;
+        Ok(())
+    }
+}
+
+/// Time since the download recorded in the stamp file at `path`. File systems may round
+/// modification times down, so the stamp holds the exact time, in nanoseconds since the epoch;
+/// the modification time is only used for a stamp without one.
+fn elapsed_since_stamp(path: &Path) -> Option<Duration> {
+    let recorded = fs::read_to_string(path).ok()?.trim().parse::<u64>().ok();
+    let stamped = match recorded {
+        Some(nanos) => UNIX_EPOCH + Duration::from_nanos(nanos),
+        None => fs::metadata(path).ok()?.modified().ok()?,
+    };
+    // a stamp in the future (clock skew) counts as just now
+    Some(SystemTime::now().duration_since(stamped).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    /// Local stand-in for the puzzle server. Serves `1\n2\n3\n` as the input for every day of
+    /// every year, to requests carrying the session cookie `secret`. Returns the base URL and a
+    /// request counter.
+    fn stand_in_server() -> (String, Arc<AtomicUsize>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let authorized = request
+                    .headers()
+                    .iter()
+                    .any(|h| h.field.equiv("Cookie") && h.value.as_str() == "session=secret");
+                let response = if !authorized {
+                    tiny_http::Response::from_string("log in first").with_status_code(400)
+                } else if request.url().contains("/day/") {
+                    tiny_http::Response::from_string("1\n2\n3\n")
+                } else {
+                    tiny_http::Response::from_string("not found").with_status_code(404)
+                };
+                let _ = request.respond(response);
+            }
+        });
+        (base_url, requests)
+    }
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_2021_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_resolve_fetches_once() {
+        let (base_url, requests) = stand_in_server();
+        let cache_dir = temp_cache_dir("resolve");
+        let manager = InputManager::new(&cache_dir)
+            .with_base_url(base_url)
+            .with_session("secret")
+            .with_min_interval(Duration::ZERO);
+
+        let path = manager.resolve(2021, 16).unwrap();
+        assert_eq!(path, cache_dir.join("2021").join("d16"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        assert_eq!(manager.resolve(2021, 16).unwrap(), path);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // the same day of another year is another input
+        assert_eq!(manager.resolve(2022, 16).unwrap(), cache_dir.join("2022").join("d16"));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, requests) = stand_in_server();
+        let cache_dir = temp_cache_dir("errors");
+        let manager = InputManager::new(&cache_dir)
+            .with_base_url(base_url)
+            .with_min_interval(Duration::ZERO);
+
+        // no session: fails without making a request
+        assert!(matches!(manager.resolve(2021, 17), Err(AocError::Fetch(_))));
+        let with_session = manager.clone().with_session("secret");
+        assert!(matches!(with_session.fetch(2021, 26), Err(AocError::Fetch(_))));
+        assert!(matches!(with_session.fetch(2014, 1), Err(AocError::Fetch(_))));
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+        // rejected session: nothing is cached
+        let manager = manager.with_session("wrong");
+        assert!(matches!(manager.resolve(2021, 17), Err(AocError::Fetch(_))));
+        assert!(!manager.cached_path(2021, 17).exists());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let (base_url, requests) = stand_in_server();
+        let cache_dir = temp_cache_dir("throttle");
+        let manager = InputManager::new(&cache_dir)
+            .with_base_url(base_url)
+            .with_session("secret")
+            .with_min_interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        manager.fetch(2021, 18).unwrap();
+        manager.fetch(2021, 19).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
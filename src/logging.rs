@@ -0,0 +1,112 @@
+/// Logging for the command line: the `tracing` events and spans the library emits, filtered, as
+/// text on stderr and optionally as JSON lines in a file.
+///
+/// Filters use the `tracing_subscriber::EnvFilter` syntax: a level like `debug`, or per-module
+/// directives like `warn,aoc_2021::y2021::d12=trace`.
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+use crate::error::{AocError, Result};
+
+/// Environment variable with the stderr filter, if the command line doesn't give one
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// Filter for stderr when neither the command line nor [`LOG_ENV`] gives one
+pub const DEFAULT_FILTER: &str = "warn";
+
+fn filter(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::try_new(directives)
+        .map_err(|e| AocError::parse(0, format!("bad log filter {:?}: {}", directives, e)))
+}
+
+/// A layer writing events that pass `directives` to `writer` as JSON, one object per line, with
+/// the spans each happened in
+pub fn json_layer<S, W>(writer: W, directives: &str) -> Result<Box<dyn Layer<S> + Send + Sync>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = fmt::layer()
+        .json()
+        .with_span_list(true)
+        .with_writer(writer)
+        .with_filter(filter(directives)?);
+    Ok(Box::new(layer))
+}
+
+/// Install the global subscriber: events passing `stderr_filter` go to stderr, or those passing
+/// `AOC_LOG`, or warnings and errors by default. With `log_file`, events passing its filter are
+/// also written to it as JSON lines.
+pub fn init(stderr_filter: Option<&str>, log_file: Option<(&Path, &str)>) -> Result<()> {
+    let directives = match stderr_filter {
+        Some(f) => f.to_string(),
+        None => std::env::var(LOG_ENV).unwrap_or_else(|_| DEFAULT_FILTER.to_string()),
+    };
+    let stderr = fmt::layer()
+        .with_writer(io::stderr)
+        .with_filter(filter(&directives)?);
+    let json = match log_file {
+        Some((path, directives)) => Some(json_layer(Mutex::new(File::create(path)?), directives)?),
+        None => None,
+    };
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(json)
+        .try_init()
+        .map_err(|e| AocError::invalid_state(format!("logging is already set up: {}", e)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::y2021::d11;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    /// A writer into a shared buffer
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_layer() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let layer = json_layer(move || writer.clone(), "aoc_2021::y2021::d11=trace").unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let grid = d11::parse_input_from_string("11111\n19991\n19191\n19991\n11111").unwrap();
+        tracing::subscriber::with_default(subscriber, || d11::simulate(&grid, 2));
+
+        let text = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<serde_json::Value> =
+            text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        let flashes: Vec<_> = events
+            .iter()
+            .filter(|e| e["fields"]["message"] == "flash")
+            .collect();
+        // the 9s flash in step 1, and nothing can flash in step 2
+        assert_eq!(flashes.len(), 9);
+        assert_eq!(flashes[0]["spans"][1]["step"], 1);
+        assert!(events.iter().any(|e| e["fields"]["message"] == "stepped"));
+
+        assert!(json_layer::<tracing_subscriber::Registry, _>(io::sink, "d12=loud").is_err());
+    }
+}
@@ -1,3 +1,721 @@
+/// Advent of Code command line runner, for every registered year
+///
+/// Puzzles are named by year and day, e.g. `2021 5`. A day on its own is from the latest year
+/// with a registered day.
+///
+/// Examples:
+///
+/// ```text
+/// $ aoc run 2021 5
+/// $ aoc run 5
+/// $ aoc run 2021
+/// $ aoc run 7 --part 2 --input inputs/2021/d7_test
+/// $ aoc run --all
+/// $ cat inputs/2021/d1 | aoc run 1 --input -
+/// $ aoc gen 1 --size 100000000 | aoc run 1 --part 1 --stream --input -
+/// $ aoc run --all --jobs 4
+/// $ aoc run --all --format jsonl
+/// $ AOC_SESSION=... aoc fetch 2021 14 15
+/// $ aoc verify
+/// $ aoc time --runs 20
+/// $ aoc compare 3 --generated 20
+/// $ aoc gen 4 --size 1000 --seed 7
+/// $ aoc examples ~/Downloads/day12.html --block 0 --block 5 --block 7
+/// $ aoc new 2022 1
+/// $ aoc render 11 octopods.gif --input inputs/2021/d11_test
+/// $ aoc play 11 --fps 20
+/// $ aoc run 12 --log aoc_2021::y2021::d12=debug --log-file d12.jsonl --log-file-filter trace
+/// ```
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use clap::{ArgEnum, Parser, Subcommand};
+
+use aoc_2021::compare::{self, Comparison};
+use aoc_2021::examples::{self, Page};
+use aoc_2021::gen;
+use aoc_2021::inputs::InputManager;
+use aoc_2021::logging;
+use aoc_2021::manifest::{self, Manifest};
+use aoc_2021::player::{self, Player};
+use aoc_2021::render;
+use aoc_2021::report::{self, Record};
+use aoc_2021::scaffold;
+use aoc_2021::solution::{self, Day, Part};
+use aoc_2021::source::Source;
+use aoc_2021::timing::{self, Timing};
+
+#[derive(Parser)]
+#[clap(name = "aoc", about = "Advent of Code solutions")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+
+    /// Log filter for stderr: a level, e.g. `debug`, or per-module directives, e.g.
+    /// `warn,aoc_2021::y2021::d12=trace`. Defaults to AOC_LOG, or `warn`
+    #[clap(long, global = true)]
+    log: Option<String>,
+
+    /// Also write logs to this file, as JSON lines
+    #[clap(long, global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Log filter for the log file
+    #[clap(long, global = true, default_value = "debug")]
+    log_file_filter: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a single day, for all days of a year, or for all days
+    Run {
+        /// Year and day to run, e.g. `2021 5`, a whole year, or a day of the latest year
+        #[clap(
+            value_name = "YEAR DAY",
+            max_values = 2,
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        puzzle: Vec<u32>,
+
+        /// Run only this part
+        #[clap(short, long, possible_values = ["1", "2"])]
+        part: Option<u32>,
+
+        /// Input file, or `-` for stdin. Defaults to `inputs/<YEAR>/d<DAY>`
+        #[clap(short, long, conflicts_with = "all")]
+        input: Option<String>,
+
+        /// Run every day of every year
+        #[clap(short, long)]
+        all: bool,
+
+        /// Number of days to run in parallel, 0 for one per CPU
+        #[clap(short, long, default_value_t = 1)]
+        jobs: usize,
+
+        /// Download missing inputs into the cache first (see `fetch`)
+        #[clap(long, conflicts_with = "input")]
+        fetch: bool,
+
+        /// Solve straight from the input a line at a time, for days that support it (2021's 1,
+        /// 2, 3 part 1, 6 and 10). Memory stays constant, except that day 10 part 2 keeps one
+        /// score per incomplete line
+        #[clap(long)]
+        stream: bool,
+
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        format: Format,
+    },
+
+    /// Download puzzle inputs into the cache, skipping ones already there.
+    ///
+    /// Needs a session cookie in AOC_SESSION. AOC_CACHE_DIR (default `inputs`) and AOC_BASE_URL
+    /// override where inputs are stored and downloaded from.
+    Fetch {
+        /// Year, defaulting to the latest with a registered day, then the days to download
+        #[clap(value_name = "YEAR DAYS", required = true)]
+        puzzle: Vec<u32>,
+    },
+
+    /// Check solutions against the manifest of known answers
+    Verify {
+        /// Only verify this year, or this day of it
+        #[clap(value_name = "YEAR DAY", max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Answer manifest
+        #[clap(short, long, default_value = manifest::DEFAULT_PATH)]
+        manifest: String,
+
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        format: Format,
+    },
+
+    /// Time parsing and solving, over repeated runs
+    Time {
+        /// Only time this year, or this day of it
+        #[clap(value_name = "YEAR DAY", max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Number of runs of each step
+        #[clap(short, long, default_value_t = 10)]
+        runs: usize,
+
+        /// Table order
+        #[clap(short, long, arg_enum, default_value = "slowest")]
+        sort: SortOrder,
+    },
+
+    /// Check that each day's alternative implementations agree with its own on the example,
+    /// puzzle and generated inputs, and time them against each other
+    Compare {
+        /// Only compare this year's implementations, or this day's
+        #[clap(value_name = "YEAR DAY", max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Number of runs of each implementation per input
+        #[clap(short, long, default_value_t = 10)]
+        runs: usize,
+
+        /// Number of generated inputs to check, on top of the example and puzzle inputs
+        #[clap(short, long, default_value_t = 5)]
+        generated: u64,
+    },
+
+    /// Extract example inputs and answers from a saved puzzle page, into test fixtures and the
+    /// manifest. Without `--block`, lists the page's example blocks.
+    Examples {
+        /// Saved puzzle page (HTML)
+        page: String,
+
+        /// Block to write as a fixture, repeat for more: the first is written to
+        /// `<YEAR>/d<DAY>_test` with the page's answers, the rest to `<YEAR>/d<DAY>_test2` and
+        /// so on
+        #[clap(short, long)]
+        block: Vec<usize>,
+
+        /// Directory to write fixtures to, in a directory for the page's year
+        #[clap(long, default_value = "inputs")]
+        dir: String,
+
+        /// Answer manifest to add entries to
+        #[clap(short, long, default_value = manifest::DEFAULT_PATH)]
+        manifest: String,
+
+        /// Overwrite fixtures that already exist with different content
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Start a new day: write a module skeleton for it, register it, and add empty inputs and a
+    /// manifest entry. The first day of a year also adds the year's module, feature and registry.
+    /// Run from the root of the crate.
+    New {
+        /// Year and day to start, or a day of the latest year
+        #[clap(value_name = "YEAR DAY", required = true, max_values = 2)]
+        puzzle: Vec<u32>,
+    },
+
+    /// Draw a day's puzzle: the vent map (5), heightmap (9) or folded paper (13) as a PNG, or
+    /// the octopods flashing (11) as an animated GIF
+    Render {
+        /// Year and day to draw, or a day of the latest year
+        #[clap(value_name = "YEAR DAY", required = true, max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Image file to write
+        output: String,
+
+        /// Input file, or `-` for stdin. Defaults to `inputs/<YEAR>/d<DAY>`
+        #[clap(short, long)]
+        input: Option<String>,
+
+        /// Pixels per grid cell
+        #[clap(short, long)]
+        scale: Option<usize>,
+    },
+
+    /// Play a day's simulation in the terminal: lanternfish (6), octopods (11) or paper folding
+    /// (13). Space pauses, arrow keys step back and forth, +/- change speed, q quits
+    Play {
+        /// Year and day to play, or a day of the latest year
+        #[clap(value_name = "YEAR DAY", required = true, max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Input file, or `-` for stdin. Defaults to `inputs/<YEAR>/d<DAY>`
+        #[clap(short, long)]
+        input: Option<String>,
+
+        /// Frames per second to start at
+        #[clap(long, default_value_t = 10.0)]
+        fps: f64,
+    },
+
+    /// Print a random input for a day, e.g. `aoc gen 5 --size 10000 | aoc run 5 --input -`
+    Gen {
+        /// Year and day to generate an input for, or a day of the latest year
+        #[clap(value_name = "YEAR DAY", required = true, max_values = 2)]
+        puzzle: Vec<u32>,
+
+        /// Roughly the number of lines or items. Defaults to about the size of a real input
+        #[clap(short, long)]
+        size: Option<usize>,
+
+        /// Random seed; the same seed gives the same input
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum Format {
+    /// Human readable text
+    Text,
+    /// A single JSON array of records
+    Json,
+    /// JSON Lines, one record per line
+    Jsonl,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum SortOrder {
+    /// Slowest median first
+    Slowest,
+    /// By day, then stage
+    Day,
+}
+
+/// Print one day's run records as text: answers to stdout, errors to stderr. With `timed`, the
+/// day's wall time follows.
+fn print_runs(records: &[Record], timed: bool) {
+    let mut reported_parse_error = false;
+    for r in records {
+        match (&r.answer, &r.error) {
+            (Some(answer), _) if answer.contains('\n') => {
+                // multi-line answers (e.g. rendered letters) start on their own line
+                println!("{} day {}, part {}:\n{}", r.year, r.day, r.part, answer);
+            }
+            (Some(answer), _) => println!("{} day {}, part {}: {}", r.year, r.day, r.part, answer),
+            (None, Some(e)) if r.solve_ms.is_none() => {
+                // the input couldn't be read or parsed, report that once for the day
+                match &e.diagnostic {
+                    _ if reported_parse_error => {}
+                    Some(diagnostic) => eprint!("{}", diagnostic),
+                    None => {
+                        let input = r.input.as_deref().unwrap_or("");
+                        eprintln!("{} day {}: {}: {}", r.year, r.day, input, e)
+                    }
+                }
+                reported_parse_error = true;
+            }
+            (None, Some(e)) => match &e.diagnostic {
+                // a streamed part reads and parses the input itself
+                Some(diagnostic) => {
+                    eprint!("{} day {}, part {}:\n{}", r.year, r.day, r.part, diagnostic)
+                }
+                None => eprintln!("{} day {}, part {}: {}", r.year, r.day, r.part, e),
+            },
+            (None, None) => {}
+        }
+    }
+    if let (true, Some(r)) = (timed, records.last()) {
+        println!("{} day {}: {:.3} ms", r.year, r.day, r.wall_ms.unwrap_or(0.0));
+    }
+}
+
+/// Print verification records as text, one line per check plus a summary
+fn print_verification(records: &[Record]) {
+    for r in records {
+        let label = format!("{} day {:>2} part {}", r.year, r.day, r.part);
+        let input = r.input.as_deref().unwrap_or("");
+        let millis = r.solve_ms.unwrap_or(0.0);
+        match r.status {
+            Some("pass") => println!("PASS     {}  {:<22} {:>10.3} ms", label, input, millis),
+            Some("fail") => println!(
+                "FAIL     {}  {:<22} {:>10.3} ms  expected {:?}, got {:?}",
+                label,
+                input,
+                millis,
+                r.expected.as_deref().unwrap_or(""),
+                r.answer.as_deref().unwrap_or("")
+            ),
+            Some("error") => println!(
+                "ERROR    {}  {:<22} {:>10.3} ms  {}",
+                label,
+                input,
+                millis,
+                r.error.as_ref().map(|e| e.to_string()).unwrap_or_default()
+            ),
+            _ => println!("MISSING  {}", label),
+        }
+    }
+    let count = |status| records.iter().filter(|r| r.status == Some(status)).count();
+    println!(
+        "{} passed, {} failed, {} missing",
+        count("pass"),
+        count("fail") + count("error"),
+        count("missing")
+    );
+}
+
+/// Print records in the given format, using `text` for the text format
+fn emit(records: &[Record], format: Format, text: fn(&[Record])) {
+    match format {
+        Format::Text => text(records),
+        Format::Json => println!("{}", report::to_json(records)),
+        Format::Jsonl => print!("{}", report::to_jsonl(records)),
+    }
+}
+
+/// `year`, or the latest year with a registered day if it's left out. Exits if there's neither.
+fn year_or_latest(year: Option<u32>) -> u32 {
+    match year.or_else(solution::latest_year) {
+        Some(year) => year,
+        None => {
+            eprintln!("no days are registered, give a year");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The year and day named by `[YEAR] DAY` arguments, the year defaulting to the latest with a
+/// registered day. Exits if there's no day.
+fn year_and_day(puzzle: &[u32]) -> (u32, u32) {
+    match solution::split_year(puzzle) {
+        (year, &[day]) => (year_or_latest(year), day),
+        _ => {
+            eprintln!("expected a day, or a year and a day");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Days named by `[YEAR] [DAY]` arguments: one day, every day of a year, or every day if there
+/// are no arguments. Exits if the day or year has nothing registered.
+fn select_days(puzzle: &[u32]) -> Vec<&'static dyn Day> {
+    let (days, name): (Vec<_>, _) = match solution::split_year(puzzle) {
+        (None, []) => return solution::days().collect(),
+        (Some(year), []) => {
+            let days = solution::days().filter(|d| d.year() == year).collect();
+            (days, year.to_string())
+        }
+        _ => {
+            let (year, day) = year_and_day(puzzle);
+            (solution::get(year, day).into_iter().collect(), format!("{} day {}", year, day))
+        }
+    };
+    if days.is_empty() {
+        eprintln!("no solution registered for {}", name);
+        std::process::exit(1);
+    }
+    days
+}
+
+/// Print a page's example blocks, numbered, followed by its example answers
+fn print_blocks(page: &Page) {
+    for (i, block) in page.blocks.iter().enumerate() {
+        println!("--- block {} ---\n{}", i, block.trim_end());
+    }
+    for (part, answer) in page.answers.iter().enumerate() {
+        let answer = answer.as_deref().unwrap_or("-");
+        println!("{} day {}, part {}: {}", page.year, page.day, part + 1, answer);
+    }
+}
+
+/// Print comparisons as a table, each variant timed relative to the day's own implementation
+fn print_comparisons(comparisons: &[Comparison]) {
+    println!(
+        "{:>4}  {:>3}  {:>4}  {:<20}  {:<10}  {:>12}  {:>7}  answer",
+        "year", "day", "part", "input", "variant", "median", "vs main"
+    );
+    let mut main_median = None;
+    for c in comparisons {
+        if c.variant == solution::MAIN {
+            main_median = Some(c.stats.median);
+        }
+        let relative = main_median.map_or(0.0, |m| {
+            c.stats.median.as_secs_f64() / m.as_secs_f64().max(f64::MIN_POSITIVE)
+        });
+        let answer = match (&c.answer, c.agrees) {
+            (Ok(a), true) if a.to_string().contains('\n') => "ok".to_string(),
+            (Ok(a), true) => a.to_string(),
+            (Ok(a), false) => format!("DISAGREES: {}", a),
+            (Err(e), true) => format!("failed: {}", e),
+            (Err(e), false) => format!("DISAGREES: {}", e),
+        };
+        println!(
+            "{:>4}  {:>3}  {:>4}  {:<20}  {:<10}  {:>12}  {:>6.2}x  {}",
+            c.year,
+            c.day,
+            c.part.number(),
+            c.input,
+            c.variant,
+            format!("{:.1?}", c.stats.median),
+            relative,
+            answer
+        );
+    }
+}
+
+/// Print timings as a table
+fn print_timings(timings: &[Timing]) {
+    println!(
+        "{:>4}  {:>3}  {:<6}  {:>12}  {:>12}  {:>12}  {:>4}",
+        "year", "day", "stage", "min", "median", "max", "runs"
+    );
+    for t in timings {
+        println!(
+            "{:>4}  {:>3}  {:<6}  {:>12}  {:>12}  {:>12}  {:>4}",
+            t.year,
+            t.day,
+            t.stage.to_string(),
+            format!("{:.1?}", t.stats.min),
+            format!("{:.1?}", t.stats.median),
+            format!("{:.1?}", t.stats.max),
+            t.stats.runs
+        );
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let cli = Cli::parse();
+    let log_file = cli.log_file.as_deref().map(|path| (path, cli.log_file_filter.as_str()));
+    if let Err(e) = logging::init(cli.log.as_deref(), log_file) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    match cli.command {
+        Command::Run {
+            puzzle,
+            part,
+            input,
+            // without a puzzle every day runs, `--all` just says so explicitly
+            all: _,
+            jobs,
+            fetch,
+            stream,
+            format,
+        } => {
+            let parts = match part.and_then(Part::from_number) {
+                Some(p) => vec![p],
+                None => Part::ALL.to_vec(),
+            };
+            // each streamed part reads the input afresh, which stdin can only do once
+            if stream && parts.len() > 1 && input.as_deref() == Some("-") {
+                eprintln!("--stream from stdin needs a --part");
+                std::process::exit(1);
+            }
+            let jobs = match jobs {
+                0 => thread::available_parallelism().map_or(1, |n| n.get()),
+                n => n,
+            };
+            let manager = InputManager::from_env();
+            // downloads are throttled, make concurrent jobs take turns
+            let fetch_lock = Mutex::new(());
+            let job = |day: &dyn Day| {
+                let source = match &input {
+                    Some(arg) => Source::from_arg(arg),
+                    None if fetch => {
+                        let _turn = fetch_lock.lock().unwrap_or_else(|e| e.into_inner());
+                        match manager.resolve(day.year(), day.day()) {
+                            Ok(path) => Source::File(path),
+                            Err(e) => {
+                                let cached = manager.cached_path(day.year(), day.day());
+                                let input = cached.display().to_string();
+                                return report::failed(day, &parts, &input, &e);
+                            }
+                        }
+                    }
+                    None => day.default_input(),
+                };
+                if stream {
+                    report::isolated(day, &parts, &source, || {
+                        report::run_streamed(day, &parts, &source)
+                    })
+                } else {
+                    report::run_isolated(day, &parts, &source)
+                }
+            };
+            let days = select_days(&puzzle);
+            let mut records = Vec::new();
+            report::run_many(&days, jobs, job, |day_records| {
+                match format {
+                    // stream each day as soon as it's ready, a JSON array has to wait for all
+                    Format::Text => print_runs(&day_records, days.len() > 1),
+                    Format::Jsonl => print!("{}", report::to_jsonl(&day_records)),
+                    Format::Json => {}
+                }
+                records.extend(day_records);
+            });
+            if let Format::Json = format {
+                println!("{}", report::to_json(&records));
+            }
+            if records.iter().any(|r| r.error.is_some()) {
+                std::process::exit(1);
+            }
+        }
+        Command::Fetch { puzzle } => {
+            let (year, days) = solution::split_year(&puzzle);
+            let year = year_or_latest(year);
+            if days.is_empty() {
+                eprintln!("no days to fetch");
+                std::process::exit(1);
+            }
+            let manager = InputManager::from_env();
+            let mut ok = true;
+            for &day in days {
+                match manager.resolve(year, day) {
+                    Ok(path) => println!("{} day {}: {}", year, day, path.display()),
+                    Err(e) => {
+                        eprintln!("{} day {}: {}", year, day, e);
+                        ok = false;
+                    }
+                }
+            }
+            if !ok {
+                std::process::exit(1);
+            }
+        }
+        Command::Verify {
+            puzzle,
+            manifest,
+            format,
+        } => {
+            let manifest = match Manifest::load(&manifest) {
+                Ok(m) => m,
+                Err(e) => {
+                    eprintln!("{}: {}", manifest, e);
+                    std::process::exit(1);
+                }
+            };
+            let outcomes: Vec<_> = select_days(&puzzle)
+                .into_iter()
+                .flat_map(|day| manifest.verify_day(day))
+                .collect();
+            let records: Vec<Record> = outcomes.iter().map(Record::from_outcome).collect();
+            emit(&records, format, print_verification);
+            if outcomes.iter().any(|o| !o.is_ok()) {
+                std::process::exit(1);
+            }
+        }
+        Command::Time { puzzle, runs, sort } => {
+            let mut ok = true;
+            let mut timings = Vec::new();
+            for day in select_days(&puzzle) {
+                let source = day.default_input();
+                let result = source
+                    .read()
+                    .and_then(|content| timing::time_day(day, &content, &Part::ALL, runs));
+                match result {
+                    Ok(t) => timings.extend(t),
+                    Err(e) => {
+                        eprintln!("{} day {}: {}: {}", day.year(), day.day(), source, e);
+                        ok = false;
+                    }
+                }
+            }
+            if let SortOrder::Slowest = sort {
+                timings.sort_by_key(|t| Reverse(t.stats.median));
+            }
+            print_timings(&timings);
+            if !ok {
+                std::process::exit(1);
+            }
+        }
+        Command::Compare {
+            puzzle,
+            runs,
+            generated,
+        } => {
+            let mut ok = true;
+            let mut comparisons = Vec::new();
+            for day in select_days(&puzzle) {
+                for (input, content) in compare::inputs(day, generated) {
+                    match compare::compare(day, &input, &content, runs) {
+                        Ok(c) => comparisons.extend(c),
+                        Err(e) => {
+                            eprintln!("{} day {}: {}: {}", day.year(), day.day(), input, e);
+                            ok = false;
+                        }
+                    }
+                }
+            }
+            if comparisons.is_empty() {
+                println!("no alternative implementations to compare");
+            } else {
+                print_comparisons(&comparisons);
+            }
+            if !ok || comparisons.iter().any(|c| !c.agrees) {
+                std::process::exit(1);
+            }
+        }
+        Command::Examples {
+            page,
+            block,
+            dir,
+            manifest,
+            force,
+        } => {
+            let result = Page::load(&page).and_then(|page| {
+                if block.is_empty() {
+                    print_blocks(&page);
+                    return Ok(());
+                }
+                let fixtures = page.fixtures(Path::new(&dir), &block)?;
+                examples::write(&fixtures, Path::new(&manifest), force)?;
+                for f in &fixtures {
+                    println!("{}", f.path.display());
+                }
+                Ok(())
+            });
+            if let Err(e) = result {
+                eprintln!("{}: {}", page, e);
+                std::process::exit(1);
+            }
+        }
+        Command::New { puzzle } => {
+            let (year, day) = year_and_day(&puzzle);
+            match scaffold::new_day(Path::new("."), year, day) {
+                Ok(changed) => {
+                    for path in changed {
+                        println!("{}", path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} day {}: {}", year, day, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Render {
+            puzzle,
+            output,
+            input,
+            scale,
+        } => {
+            let (year, day) = year_and_day(&puzzle);
+            let source = match input {
+                Some(arg) => Source::from_arg(&arg),
+                None => Source::File(format!("inputs/{}/d{}", year, day).into()),
+            };
+            let result = source
+                .read()
+                .and_then(|content| render::render_day(year, day, &content, &output, scale));
+            if let Err(e) = result {
+                eprintln!("{} day {}: {}: {}", year, day, source, e);
+                std::process::exit(1);
+            }
+        }
+        Command::Play { puzzle, input, fps } => {
+            let (year, day) = year_and_day(&puzzle);
+            let source = match input {
+                Some(arg) => Source::from_arg(&arg),
+                None => Source::File(format!("inputs/{}/d{}", year, day).into()),
+            };
+            let result = source
+                .read()
+                .and_then(|content| player::day_frames(year, day, &content))
+                .and_then(|frames| Player::new(frames).with_fps(fps).play());
+            if let Err(e) = result {
+                eprintln!("{} day {}: {}: {}", year, day, source, e);
+                std::process::exit(1);
+            }
+        }
+        Command::Gen { puzzle, size, seed } => {
+            let (year, day) = year_and_day(&puzzle);
+            let size = size.or_else(|| gen::default_size(year, day));
+            match size.and_then(|size| gen::generate(year, day, size, seed)) {
+                Some(input) => print!("{}", input),
+                None => {
+                    eprintln!("no generator for {} day {}", year, day);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
 }
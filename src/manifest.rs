@@ -0,0 +1,291 @@
+/// Manifest of known answers, and verification of the registered solutions against it.
+///
+/// The manifest is a TOML file with one `[[answer]]` table per (year, day, input file):
+///
+/// ```toml
+/// [[answer]]
+/// year = 2021
+/// day = 1
+/// input = "inputs/2021/d1_test"
+/// part1 = 7
+/// part2 = 5
+/// ```
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::report::ErrorDetail;
+use crate::solution::{Day, Part};
+use crate::source::Source;
+use crate::util;
+
+/// Default manifest location, relative to the crate root
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    pub entries: Vec<Entry>,
+}
+
+/// Expected answers for one day of one year on one input file
+#[derive(Debug, Deserialize)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub input: String,
+    pub part1: Option<Expected>,
+    pub part2: Option<Expected>,
+}
+
+/// An expected answer: most are integers, a few are text
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Expected {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Int(n) => write!(f, "{}", n),
+            Expected::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Integers match integers of any size and text matches text, ignoring trailing whitespace.
+/// Answers too big for a TOML integer are written as strings, so text matches them too.
+impl PartialEq<Expected> for Answer {
+    fn eq(&self, expected: &Expected) -> bool {
+        match (self.as_int(), expected) {
+            (Some(n), Expected::Int(e)) => n == *e as i128,
+            (None, Expected::Int(_)) => false,
+            (_, Expected::Text(e)) => self.to_string().trim_end() == e.trim_end(),
+        }
+    }
+}
+
+impl Entry {
+    pub fn expected(&self, part: Part) -> Option<&Expected> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// Result of checking one part of one day on one input
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail { actual: String },
+    /// Parsing or solving failed
+    Error(ErrorDetail),
+    /// The manifest has no answer for this part of the day
+    Missing,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    /// Input file, `None` for missing answers
+    pub input: Option<String>,
+    /// Expected answer, `None` for missing answers
+    pub expected: Option<String>,
+    pub status: Status,
+    /// Time taken to solve the part, excluding parsing
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Pass | Status::Missing)
+    }
+}
+
+impl Manifest {
+    pub fn load(path: &str) -> Result<Manifest> {
+        Manifest::from_string(&util::read_to_string(path)?)
+    }
+
+    pub fn from_string(content: &str) -> Result<Manifest> {
+        toml::from_str(content).map_err(|e| {
+            let line = e.line_col().map(|(l, _c)| l + 1).unwrap_or(0);
+            AocError::parse(line, e.to_string())
+        })
+    }
+
+    /// Entries for the given day of the given year, in manifest order
+    pub fn entries_for(&self, year: u32, day: u32) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |e| e.year == year && e.day == day)
+    }
+
+    /// Check every manifest entry for `day`. Parts of the day that have no entry at all are
+    /// reported as [`Status::Missing`].
+    pub fn verify_day(&self, day: &dyn Day) -> Vec<Outcome> {
+        let mut outcomes = Vec::new();
+        for entry in self.entries_for(day.year(), day.day()) {
+            let source = Source::from_arg(&entry.input);
+            let parsed = day.parse_source(&source);
+            for part in Part::ALL {
+                let expected = match entry.expected(part) {
+                    Some(e) => e,
+                    None => continue,
+                };
+                let start = Instant::now();
+                let result = match &parsed {
+                    Ok(p) => p.solve(part).map_err(|e| ErrorDetail::from(&e)),
+                    Err(e) => Err(ErrorDetail::from(e)),
+                };
+                let elapsed = start.elapsed();
+                let status = match result {
+                    Ok(actual) if actual == *expected => Status::Pass,
+                    Ok(actual) => Status::Fail {
+                        actual: actual.to_string(),
+                    },
+                    Err(e) => Status::Error(e),
+                };
+                outcomes.push(Outcome {
+                    year: day.year(),
+                    day: day.day(),
+                    part,
+                    input: Some(entry.input.clone()),
+                    expected: Some(expected.to_string()),
+                    status,
+                    elapsed,
+                });
+            }
+        }
+        for part in Part::ALL {
+            if !self.entries_for(day.year(), day.day()).any(|e| e.expected(part).is_some()) {
+                outcomes.push(Outcome {
+                    year: day.year(),
+                    day: day.day(),
+                    part,
+                    input: None,
+                    expected: None,
+                    status: Status::Missing,
+                    elapsed: Duration::ZERO,
+                });
+            }
+        }
+        outcomes
+    }
+}
+
+/// Check one of this crate's days against the default manifest, panicking on any failure. Used
+/// by the per-day unit tests in place of hard-coded answers.
+#[cfg(test)]
+pub(crate) fn assert_answers(year: u32, day: u32) {
+    let manifest = Manifest::load(DEFAULT_PATH).unwrap();
+    let day = crate::solution::get(year, day).unwrap();
+    let failures: Vec<String> = manifest
+        .verify_day(day)
+        .into_iter()
+        .filter_map(|o| match o.status {
+            Status::Pass => None,
+            Status::Missing => Some(format!("part {}: no answers in manifest", o.part)),
+            Status::Fail { actual } => Some(format!(
+                "part {} on {}: expected {:?}, got {:?}",
+                o.part,
+                o.input.unwrap_or_default(),
+                o.expected.unwrap_or_default(),
+                actual
+            )),
+            Status::Error(e) => {
+                Some(format!("part {} on {}: {}", o.part, o.input.unwrap_or_default(), e))
+            }
+        })
+        .collect();
+    assert!(failures.is_empty(), "day {}:\n{}", day.day(), failures.join("\n"));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::from_string(
+            r#"
+            [[answer]]
+            year = 2021
+            day = 13
+            input = "inputs/2021/d13_test"
+            part1 = 17
+            part2 = """
+            #.#
+            """
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.entries.len(), 1);
+        assert_eq!(manifest.entries[0].expected(Part::One), Some(&Expected::Int(17)));
+        assert!(matches!(manifest.entries[0].expected(Part::Two), Some(Expected::Text(_))));
+
+        assert!(matches!(
+            Manifest::from_string("[[answer]]\nyear = 2021\nday = 1\ninput = 3\n"),
+            Err(AocError::Parse { .. })
+        ));
+        // every entry names its year
+        assert!(Manifest::from_string("[[answer]]\nday = 1\ninput = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let manifest = Manifest::from_string(
+            r#"
+            [[answer]]
+            year = 2021
+            day = 1
+            input = "inputs/2021/d1_test"
+            part1 = 7
+
+            [[answer]]
+            year = 2021
+            day = 1
+            input = "inputs/2021/d1_test"
+            part1 = 8
+
+            [[answer]]
+            year = 2021
+            day = 1
+            input = "inputs/no_such_file"
+            part1 = 7
+
+            [[answer]]
+            year = 2022
+            day = 1
+            input = "inputs/2022/d1_test"
+            part2 = 1
+            "#,
+        )
+        .unwrap();
+        let outcomes = manifest.verify_day(crate::solution::get(2021, 1).unwrap());
+        assert_eq!(outcomes.len(), 4);
+        assert!(matches!(outcomes[0].status, Status::Pass));
+        assert!(matches!(outcomes[1].status, Status::Fail { .. }));
+        assert!(matches!(outcomes[2].status, Status::Error(_)));
+        assert!(matches!(outcomes[3].status, Status::Missing));
+        assert_eq!(outcomes[3].part, Part::Two);
+    }
+
+    #[test]
+    fn test_answer_matches_expected() {
+        assert_eq!(Answer::Int(17), Expected::Int(17));
+        assert_ne!(Answer::Int(17), Expected::Int(18));
+        assert_ne!(Answer::Text("17".to_string()), Expected::Int(17));
+        let big = Answer::from(u64::MAX);
+        assert_eq!(big, Expected::Text(u64::MAX.to_string()));
+        assert_ne!(big, Expected::Int(-1));
+        let letters = Answer::from("#.#\n.#.\n");
+        assert_eq!(letters, Expected::Text("#.#\n.#.".to_string()));
+    }
+}
@@ -0,0 +1,354 @@
+/// Terminal player for simulations: redraws frames in place with ANSI colors, and lets you pause,
+/// step, rewind and change speed.
+///
+/// Frames are pre-rendered text, so anything can be played: [`grid_frame`] draws any `Grid<T>`
+/// with a color map, [`bars_frame`] draws a bar chart. Keys:
+///
+/// - space: pause or resume
+/// - right / `l`, left / `h`: step forward or back one frame, pausing
+/// - home / `0`, end / `$`: jump to the first or last frame
+/// - `+` / `-`: double or halve the frame rate
+/// - `q` / escape: quit
+///
+/// When stdout isn't a terminal, the frames are printed one after another instead. Frames larger
+/// than the terminal are clipped.
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::style::{Color, Stylize};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::error::{AocError, Result};
+use crate::grid::Grid;
+use crate::render::{gradient, Rgb};
+use crate::y2021::{d11, d13, d6};
+
+/// One frame: a caption and the picture under it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub body: String,
+}
+
+impl Frame {
+    pub fn new<C: Into<String>, B: Into<String>>(caption: C, body: B) -> Self {
+        Frame {
+            caption: caption.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// How a grid cell is drawn: a glyph in a color, optionally bold
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Rgb,
+    pub bold: bool,
+}
+
+impl Cell {
+    /// A solid block in `color`
+    pub fn block(color: Rgb) -> Self {
+        Cell {
+            glyph: '█',
+            color,
+            bold: false,
+        }
+    }
+}
+
+/// Draw `grid` with ANSI colors, two columns per cell so that cells come out about square
+pub fn grid_frame<T, F: Fn(&T) -> Cell>(grid: &Grid<T>, cell: F) -> String {
+    let mut body = String::new();
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            let c = cell(grid.get(row, col).unwrap());
+            let [r, g, b] = c.color;
+            let glyphs = format!("{0}{0}", c.glyph).with(Color::Rgb { r, g, b });
+            body += &if c.bold { glyphs.bold() } else { glyphs }.to_string();
+        }
+        body.push('\n');
+    }
+    body
+}
+
+/// Draw a horizontal bar chart of `bars`, scaled so the longest is `width` wide
+pub fn bars_frame(bars: &[(String, u64)], width: usize, color: Rgb) -> String {
+    let max = bars.iter().map(|b| b.1).max().unwrap_or(0).max(1);
+    let label_width = bars.iter().map(|b| b.0.len()).max().unwrap_or(0);
+    let [r, g, b] = color;
+    bars.iter()
+        .map(|(label, value)| {
+            let len = (*value as f64 / max as f64 * width as f64).round() as usize;
+            let bar = "█".repeat(len).with(Color::Rgb { r, g, b });
+            format!("{:>w$} {} {}\n", label, bar, value, w = label_width)
+        })
+        .collect()
+}
+
+/// Playback position and speed, separate from the terminal so it can be tested
+#[derive(Clone, Debug, PartialEq)]
+struct Playback {
+    frame: usize,
+    len: usize,
+    paused: bool,
+    fps: f64,
+}
+
+impl Playback {
+    const MIN_FPS: f64 = 0.25;
+    const MAX_FPS: f64 = 120.0;
+
+    /// Apply a key press; returns false to quit
+    fn key(&mut self, code: KeyCode) -> bool {
+        let last = self.len.saturating_sub(1);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => {
+                // resuming at the end starts over
+                if self.paused && self.frame == last {
+                    self.frame = 0;
+                }
+                self.paused = !self.paused;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.paused = true;
+                self.frame = (self.frame + 1).min(last);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.paused = true;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            KeyCode::Home | KeyCode::Char('0') => self.frame = 0,
+            KeyCode::End | KeyCode::Char('$') => self.frame = last,
+            KeyCode::Char('+' | '=') => self.fps = (self.fps * 2.0).min(Self::MAX_FPS),
+            KeyCode::Char('-') => self.fps = (self.fps / 2.0).max(Self::MIN_FPS),
+            _ => {}
+        }
+        true
+    }
+
+    /// Advance one frame if playing, pausing on the last one
+    fn tick(&mut self) {
+        if !self.paused {
+            if self.frame + 1 < self.len {
+                self.frame += 1;
+            } else {
+                self.paused = true;
+            }
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+}
+
+pub struct Player {
+    frames: Vec<Frame>,
+    fps: f64,
+}
+
+impl Player {
+    pub fn new(frames: Vec<Frame>) -> Self {
+        Player { frames, fps: 10.0 }
+    }
+
+    /// Starting frame rate, 10 by default
+    pub fn with_fps(mut self, fps: f64) -> Self {
+        self.fps = fps.clamp(Playback::MIN_FPS, Playback::MAX_FPS);
+        self
+    }
+
+    /// Play the frames until the user quits, or print them all if stdout isn't a terminal
+    pub fn play(&self) -> Result<()> {
+        let mut stdout = io::stdout();
+        if !stdout.is_terminal() {
+            for frame in &self.frames {
+                write!(stdout, "{}\n{}\n", frame.caption, frame.body)?;
+            }
+            return Ok(());
+        }
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, terminal::DisableLineWrap, cursor::Hide)?;
+        let result = self.run(&mut stdout);
+        // restore the terminal even if drawing failed
+        execute!(stdout, cursor::Show, terminal::EnableLineWrap, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn run<W: Write>(&self, out: &mut W) -> Result<()> {
+        let mut playback = Playback {
+            frame: 0,
+            len: self.frames.len(),
+            paused: false,
+            fps: self.fps,
+        };
+        let Some(_) = self.frames.first() else {
+            return Ok(());
+        };
+        loop {
+            self.draw(out, &playback)?;
+            let deadline = Instant::now() + playback.interval();
+            // wait out the frame, handling keys as they come
+            loop {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                if !playback.paused && timeout.is_zero() {
+                    playback.tick();
+                    break;
+                }
+                let wait = if playback.paused { Duration::from_secs(60) } else { timeout };
+                if event::poll(wait)? {
+                    if let Event::Key(KeyEvent { code, kind, .. }) = event::read()? {
+                        if kind == KeyEventKind::Release {
+                            continue;
+                        }
+                        if !playback.key(code) {
+                            return Ok(());
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw<W: Write>(&self, out: &mut W, playback: &Playback) -> Result<()> {
+        let frame = &self.frames[playback.frame];
+        queue!(out, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All))?;
+        let status = format!(
+            "{}  [{}/{}  {} fps{}]  space pause, ←/→ step, +/- speed, q quit",
+            frame.caption,
+            playback.frame + 1,
+            playback.len,
+            playback.fps,
+            if playback.paused { ", paused" } else { "" }
+        );
+        let (_, height) = terminal::size()?;
+        let lines = std::iter::once(status.as_str()).chain(frame.body.lines());
+        // raw mode doesn't translate newlines into carriage returns
+        for line in lines.take(height as usize) {
+            write!(out, "{}\r\n", line)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Frames of `day` of `year`'s puzzle on `content`. So far, for 2021:
+///
+/// - day 6: lanternfish per timer value, for each of the 256 days
+/// - day 11: the octopods, until a few steps after they synchronize, with flashes in bold white
+/// - day 13: the paper before and after each fold
+pub fn day_frames(year: u32, day: u32, content: &str) -> Result<Vec<Frame>> {
+    match (year, day) {
+        (2021, 6) => {
+            let maps = d6::populations(&d6::parse_input_from_string(content)?, 256)?;
+            let frames = maps.iter().enumerate().map(|(i, map)| {
+                let bars: Vec<(String, u64)> =
+                    map.iter().enumerate().map(|(t, &n)| (format!("timer {}", t), n)).collect();
+                let caption = format!("day {}: {} fish", i, map.iter().sum::<u64>());
+                Frame::new(caption, bars_frame(&bars, 60, gradient(0.5)))
+            });
+            Ok(frames.collect())
+        }
+        (2021, 11) => {
+            let grid = d11::parse_input_from_string(content)?;
+            let steps = d11::d11_part2(&grid).map_or(200, |s| s + 5).min(1000);
+            let cell = |&e: &u32| match e {
+                0 => Cell { bold: true, ..Cell::block([255, 255, 255]) },
+                e => Cell::block(gradient(e as f64 / 12.0)),
+            };
+            let frames = d11::frames(&grid, steps).into_iter().enumerate().map(|(i, g)| {
+                let flashes = g.iter().filter(|&&e| e == 0).count();
+                Frame::new(format!("step {}: {} flashes", i, flashes), grid_frame(&g, cell))
+            });
+            Ok(frames.collect())
+        }
+        (2021, 13) => {
+            let manual = d13::parse_input_from_string(content)?;
+            let steps = d13::fold_steps(&manual)?;
+            let captions = std::iter::once("unfolded".to_string())
+                .chain(manual.folds.iter().map(|f| f.to_string()));
+            let cell = |&dot: &bool| Cell::block(if dot { [255, 255, 255] } else { [20, 20, 40] });
+            let frames = steps.iter().zip(captions).map(|(g, caption)| {
+                Frame::new(format!("{} ({}x{})", caption, g.cols, g.rows), grid_frame(g, cell))
+            });
+            Ok(frames.collect())
+        }
+        _ => Err(AocError::render(format!("no player for {} day {}", year, day))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn playback() -> Playback {
+        Playback {
+            frame: 0,
+            len: 3,
+            paused: false,
+            fps: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_playback() {
+        let mut p = playback();
+        p.tick();
+        p.tick();
+        assert_eq!((p.frame, p.paused), (2, false));
+        p.tick();
+        assert_eq!((p.frame, p.paused), (2, true));
+
+        // space at the end starts over
+        assert!(p.key(KeyCode::Char(' ')));
+        assert_eq!((p.frame, p.paused), (0, false));
+
+        // stepping pauses, and stays in bounds
+        p.key(KeyCode::Left);
+        assert_eq!((p.frame, p.paused), (0, true));
+        p.key(KeyCode::Right);
+        p.key(KeyCode::Right);
+        p.key(KeyCode::Right);
+        assert_eq!(p.frame, 2);
+        p.tick();
+        assert_eq!(p.frame, 2);
+
+        p.key(KeyCode::Char('+'));
+        assert_eq!(p.fps, 20.0);
+        p.key(KeyCode::Home);
+        assert_eq!(p.frame, 0);
+        assert!(!p.key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_frames() {
+        let grid = Grid::from_rows(vec![vec![0, 1]]).unwrap();
+        let body = grid_frame(&grid, |&v| Cell::block([255 * v, 0, 0]));
+        assert_eq!(body.lines().count(), 1);
+        assert!(body.contains("\u{1b}[38;2;255;0;0m██"));
+
+        let bars = bars_frame(&[("0".to_string(), 2), ("10".to_string(), 4)], 4, [0, 0, 255]);
+        let lines: Vec<&str> = bars.lines().collect();
+        assert!(lines[0].starts_with(" 0 ") && lines[0].ends_with(" 2"));
+        assert_eq!(lines[1].matches('█').count(), 4);
+    }
+
+    #[test]
+    fn test_day_frames() {
+        let content = crate::util::read_to_string("inputs/2021/d13_test").unwrap();
+        let frames = day_frames(2021, 13, &content).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].caption, "fold along y=7 (11x7)");
+        assert_eq!(frames[2].body.lines().count(), 7);
+
+        assert_eq!(day_frames(2021, 6, "3,4,3,1,2").unwrap().len(), 257);
+        assert!(matches!(day_frames(2021, 1, ""), Err(AocError::Render(_))));
+        assert!(matches!(day_frames(2022, 6, "3,4,3,1,2"), Err(AocError::Render(_))));
+    }
+}
@@ -0,0 +1,121 @@
+/// Python extension module: every day's solver, and a few of the grid puzzles with their grids
+/// as nested lists. Build it into the current virtualenv with `maturin develop --release`, then
+///
+/// ```text
+/// >>> import aoc_2021
+/// >>> aoc_2021.solve(1, 2, open("inputs/2021/d1").read())
+/// >>> aoc_2021.solve(1, 2, open("inputs/2021/d1").read(), year=2021)
+/// >>> frames = aoc_2021.octopus_frames([[1, 1, 1], [1, 9, 1], [1, 1, 1]], 10)
+/// ```
+///
+/// Parse errors are raised as `ValueError`, I/O errors as `OSError` and anything else the solvers
+/// return as `RuntimeError`. A panic becomes a `pyo3_runtime.PanicException` rather than taking the
+/// interpreter down; the conversions live in `aoc_core`, with its `python` feature.
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::grid::Grid;
+use crate::solution::{self, Part};
+use crate::y2021::{d11, d13, d9};
+
+/// Solve part 1 or 2 of `day` on the puzzle input text `input`, as a string. The day is from
+/// `year`, or the latest year with solutions if it's left out.
+#[pyfunction]
+#[pyo3(signature = (day, part, input, year = None))]
+fn solve(py: Python<'_>, day: u32, part: u32, input: &str, year: Option<u32>) -> PyResult<String> {
+    let year = year
+        .or_else(solution::latest_year)
+        .ok_or_else(|| PyValueError::new_err("no solutions, give a year"))?;
+    let solution = solution::get(year, day)
+        .ok_or_else(|| PyValueError::new_err(format!("no solution for {} day {}", year, day)))?;
+    let part = Part::from_number(part)
+        .ok_or_else(|| PyValueError::new_err(format!("no part {}", part)))?;
+    // solving can take a while, let other Python threads run meanwhile
+    let answer = py.allow_threads(|| solution.parse(input).and_then(|parsed| parsed.solve(part)));
+    Ok(answer?.to_string())
+}
+
+/// (year, day) pairs with a solution, in order
+#[pyfunction]
+fn days() -> Vec<(u32, u32)> {
+    solution::days().map(|d| (d.year(), d.day())).collect()
+}
+
+/// Day 9's heightmap
+#[pyfunction]
+fn heightmap(input: &str) -> PyResult<Grid<d9::Height>> {
+    Ok(d9::parse_input_from_string(input)?)
+}
+
+/// Day 11's octopus energies after each of `steps` steps, starting with `grid` itself
+#[pyfunction]
+fn octopus_frames(grid: d11::OctoGrid, steps: usize) -> Vec<d11::OctoGrid> {
+    d11::frames(&grid, steps)
+}
+
+/// Day 13's paper of dots folded along `axis`, "x" or "y", at `position`, which must be on the
+/// paper
+#[pyfunction]
+fn fold(grid: Grid<bool>, axis: &str, position: usize) -> PyResult<Grid<bool>> {
+    let (fold, size) = match axis {
+        "x" => (d13::Fold::X(position), grid.cols),
+        "y" => (d13::Fold::Y(position), grid.rows),
+        _ => return Err(PyValueError::new_err(format!("bad fold axis: {}", axis))),
+    };
+    if position >= size {
+        return Err(PyValueError::new_err(format!(
+            "fold along {}={} is off a {}x{} paper",
+            axis, position, grid.cols, grid.rows
+        )));
+    }
+    Ok(d13::fold(&grid, fold)?)
+}
+
+#[pymodule]
+fn aoc_2021(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(heightmap, m)?)?;
+    m.add_function(wrap_pyfunction!(octopus_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(fold, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::exceptions::PyFileNotFoundError;
+    use pyo3::types::PyList;
+
+    #[test]
+    fn test_python() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let example = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+            assert_eq!(solve(py, 1, 2, example, None).unwrap(), "5");
+            assert_eq!(solve(py, 1, 2, example, Some(2021)).unwrap(), "5");
+            let missing = solve(py, 1, 2, example, Some(2015)).unwrap_err();
+            assert!(missing.is_instance_of::<PyValueError>(py));
+            assert!(solve(py, 1, 3, example, None).unwrap_err().is_instance_of::<PyValueError>(py));
+            assert!(solve(py, 1, 1, "x", None).unwrap_err().is_instance_of::<PyValueError>(py));
+            let io = PyErr::from(crate::util::read_to_string("inputs/no_such_file").unwrap_err());
+            assert!(io.is_instance_of::<PyFileNotFoundError>(py));
+
+            // grids round trip through nested lists
+            let rows = PyList::new_bound(py, [vec![true, false, true], vec![false, false, true]]);
+            let grid: Grid<bool> = rows.extract().unwrap();
+            assert_eq!((grid.rows, grid.cols), (2, 3));
+            let folded = fold(grid, "x", 1).unwrap().into_py(py);
+            assert_eq!(folded.extract::<Vec<Vec<bool>>>(py).unwrap(), vec![vec![true], vec![true]]);
+            // folds off the paper are errors, not allocations
+            let grid: Grid<bool> = rows.extract().unwrap();
+            let off = fold(grid.clone(), "x", 100_000_000_000).unwrap_err();
+            assert!(off.is_instance_of::<PyValueError>(py));
+            assert!(fold(grid.clone(), "y", 2).unwrap_err().is_instance_of::<PyValueError>(py));
+            assert!(fold(grid, "z", 0).unwrap_err().is_instance_of::<PyValueError>(py));
+
+            let ragged = PyList::new_bound(py, [vec![1], vec![2, 3]]);
+            assert!(ragged.extract::<Grid<u32>>().is_err());
+        });
+    }
+}
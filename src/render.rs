@@ -0,0 +1,290 @@
+/// Rendering of [`Grid`]s as PNG images, and of sequences of them as animated GIFs.
+///
+/// Colors come from a caller supplied map from cell values to RGB; [`gradient`] and [`two_tone`]
+/// cover the common cases. Each cell is drawn as a `scale` by `scale` square of pixels.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::error::{AocError, Result};
+use crate::grid::Grid;
+use crate::y2021::{d11, d13, d5, d9};
+
+pub type Rgb = [u8; 3];
+
+/// Color for `t` from 0 to 1 on a dark blue, teal, yellow scale. `t` outside of that is clamped.
+pub fn gradient(t: f64) -> Rgb {
+    const STOPS: [Rgb; 3] = [[30, 20, 80], [30, 150, 140], [250, 230, 40]];
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) } * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let f = t - i as f64;
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+    [0, 1, 2].map(|c| mix(STOPS[i][c], STOPS[i + 1][c]))
+}
+
+/// Color map for boolean grids
+pub fn two_tone(on: Rgb, off: Rgb) -> impl Fn(&bool) -> Rgb {
+    move |&b| if b { on } else { off }
+}
+
+/// Pixel dimensions of `grid` at `scale`, or an error if they don't fit in `max`
+fn dimensions<T>(grid: &Grid<T>, scale: usize, max: usize) -> Result<(usize, usize)> {
+    match (grid.cols.checked_mul(scale), grid.rows.checked_mul(scale)) {
+        (Some(width), Some(height)) if (1..=max).contains(&width) && (1..=max).contains(&height) => {
+            Ok((width, height))
+        }
+        _ => Err(AocError::render(format!(
+            "a {}x{} grid at scale {} doesn't fit in an image",
+            grid.cols, grid.rows, scale
+        ))),
+    }
+}
+
+/// RGB bytes of `grid`, row by row
+fn pixels<T, F: Fn(&T) -> Rgb>(grid: &Grid<T>, scale: usize, color: F) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(grid.rows * grid.cols * scale * scale * 3);
+    for row in 0..grid.rows {
+        let line: Vec<u8> = (0..grid.cols)
+            .flat_map(|col| {
+                let rgb = color(grid.get(row, col).unwrap());
+                (0..scale).flat_map(move |_| rgb)
+            })
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    pixels
+}
+
+/// Encode `grid` as a PNG
+pub fn write_png<T, W, F>(out: W, grid: &Grid<T>, scale: usize, color: F) -> Result<()>
+where
+    W: Write,
+    F: Fn(&T) -> Rgb,
+{
+    let (width, height) = dimensions(grid, scale, u32::MAX as usize)?;
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer
+        .write_image_data(&pixels(grid, scale, color))
+        .map_err(png_error)?;
+    writer.finish().map_err(png_error)
+}
+
+pub fn save_png<T, F, P>(path: P, grid: &Grid<T>, scale: usize, color: F) -> Result<()>
+where
+    F: Fn(&T) -> Rgb,
+    P: AsRef<Path>,
+{
+    write_png(BufWriter::new(File::create(path)?), grid, scale, color)
+}
+
+fn png_error(e: png::EncodingError) -> AocError {
+    match e {
+        png::EncodingError::IoError(e) => AocError::Io(e),
+        e => AocError::render(e.to_string()),
+    }
+}
+
+fn gif_error(e: gif::EncodingError) -> AocError {
+    match e {
+        gif::EncodingError::Io(e) => AocError::Io(e),
+        e => AocError::render(e.to_string()),
+    }
+}
+
+/// Records grids of the same size as frames of a looping animated GIF
+pub struct GifRecorder<W: Write> {
+    encoder: gif::Encoder<W>,
+    rows: usize,
+    cols: usize,
+    scale: usize,
+    /// Time each frame is shown, in hundredths of a second
+    delay: u16,
+}
+
+impl<W: Write> GifRecorder<W> {
+    /// Start an animation of `rows` by `cols` grids, showing each frame for `delay_ms`
+    pub fn new(out: W, rows: usize, cols: usize, scale: usize, delay_ms: u16) -> Result<Self> {
+        let (width, height) = dimensions(&Grid::new(rows, cols, ()), scale, u16::MAX as usize)?;
+        let mut encoder =
+            gif::Encoder::new(out, width as u16, height as u16, &[]).map_err(gif_error)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+        Ok(GifRecorder {
+            encoder,
+            rows,
+            cols,
+            scale,
+            delay: delay_ms / 10,
+        })
+    }
+
+    /// Add `grid` as the next frame
+    pub fn frame<T, F: Fn(&T) -> Rgb>(&mut self, grid: &Grid<T>, color: F) -> Result<()> {
+        if (grid.rows, grid.cols) != (self.rows, self.cols) {
+            return Err(AocError::render(format!(
+                "frame is {}x{}, the animation is {}x{}",
+                grid.cols, grid.rows, self.cols, self.rows
+            )));
+        }
+        let rgb = pixels(grid, self.scale, color);
+        let (width, height) = ((self.cols * self.scale) as u16, (self.rows * self.scale) as u16);
+        // grid maps rarely use more than 256 colors, so index them exactly when possible
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut indices = Vec::with_capacity(rgb.len() / 3);
+        for px in rgb.chunks(3) {
+            let px = [px[0], px[1], px[2]];
+            let i = match palette.iter().position(|&c| c == px) {
+                Some(i) => i,
+                None => {
+                    palette.push(px);
+                    palette.len() - 1
+                }
+            };
+            if i > 255 {
+                break;
+            }
+            indices.push(i as u8);
+        }
+        let mut frame = if palette.len() <= 256 {
+            gif::Frame::from_palette_pixels(width, height, indices, palette.concat(), None)
+        } else {
+            gif::Frame::from_rgb_speed(width, height, &rgb, 10)
+        };
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame).map_err(gif_error)
+    }
+
+    /// Finish the animation, returning the writer
+    pub fn finish(self) -> Result<W> {
+        Ok(self.encoder.into_inner()?)
+    }
+}
+
+/// Save `frames`, all the same size, as an animated GIF
+pub fn save_gif<T, F, P>(
+    path: P,
+    frames: &[Grid<T>],
+    scale: usize,
+    delay_ms: u16,
+    color: F,
+) -> Result<()>
+where
+    F: Fn(&T) -> Rgb,
+    P: AsRef<Path>,
+{
+    let first = frames.first().ok_or_else(|| AocError::render("no frames"))?;
+    let out = BufWriter::new(File::create(path)?);
+    let mut recorder = GifRecorder::new(out, first.rows, first.cols, scale, delay_ms)?;
+    for grid in frames {
+        recorder.frame(grid, &color)?;
+    }
+    recorder.finish()?.flush()?;
+    Ok(())
+}
+
+/// Render a picture of `day` of `year`'s puzzle on `content` to `path`. So far, for 2021:
+///
+/// - day 5: PNG of the vent map, including diagonal vents
+/// - day 9: PNG of the heightmap
+/// - day 11: animated GIF of the octopods, until a few steps after they synchronize
+/// - day 13: PNG of the paper after all folds
+///
+/// Without a `scale`, each day uses one that gives a picture a few hundred pixels across.
+pub fn render_day<P>(
+    year: u32,
+    day: u32,
+    content: &str,
+    path: P,
+    scale: Option<usize>,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    match (year, day) {
+        (2021, 5) => {
+            let map = d5::vent_map(&d5::parse_input_from_string(content)?, true);
+            let max = map.iter().copied().max().unwrap_or(0).max(1);
+            let color = |&n: &usize| match n {
+                0 => [0, 0, 0],
+                n => gradient(n as f64 / max as f64),
+            };
+            save_png(path, &map, scale.unwrap_or(1), color)
+        }
+        (2021, 9) => {
+            let heights = d9::parse_input_from_string(content)?;
+            save_png(path, &heights, scale.unwrap_or(4), |&h| gradient(h as f64 / 9.0))
+        }
+        (2021, 11) => {
+            let grid = d11::parse_input_from_string(content)?;
+            let steps = d11::d11_part2(&grid).map_or(200, |s| s + 5).min(1000);
+            // octopods that just flashed are white, the rest glow brighter as they charge
+            let color = |&e: &u32| if e == 0 { [255, 255, 255] } else { gradient(e as f64 / 12.0) };
+            save_gif(path, &d11::frames(&grid, steps), scale.unwrap_or(20), 100, color)
+        }
+        (2021, 13) => {
+            let paper = d13::d13_part2(&d13::parse_input_from_string(content)?)?;
+            save_png(path, &paper, scale.unwrap_or(10), two_tone([255, 255, 255], [20, 20, 40]))
+        }
+        _ => Err(AocError::render(format!("no renderer for {} day {}", year, day))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        assert_eq!(gradient(0.0), [30, 20, 80]);
+        assert_eq!(gradient(0.5), [30, 150, 140]);
+        assert_eq!(gradient(1.0), [250, 230, 40]);
+        assert_eq!(gradient(7.0), gradient(1.0));
+        assert_eq!(gradient(-1.0), gradient(0.0));
+    }
+
+    #[test]
+    fn test_png() {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, false]]).unwrap();
+        let mut out = Vec::new();
+        write_png(&mut out, &grid, 3, two_tone([255, 0, 0], [0, 0, 0])).unwrap();
+
+        let decoder = png::Decoder::new(out.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        assert_eq!(&buf[..3], &[255, 0, 0]);
+        assert_eq!(&buf[3 * 3..3 * 4], &[0, 0, 0]);
+
+        let empty: Grid<bool> = Grid::new(0, 0, false);
+        let result = write_png(&mut Vec::new(), &empty, 1, two_tone([0; 3], [0; 3]));
+        assert!(matches!(result, Err(AocError::Render(_))));
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames: Vec<Grid<u32>> = (0..3).map(|i| Grid::new(2, 3, i)).collect();
+        let mut recorder = GifRecorder::new(Vec::new(), 2, 3, 2, 50).unwrap();
+        for grid in &frames {
+            recorder.frame(grid, |&v| gradient(v as f64 / 2.0)).unwrap();
+        }
+        assert!(recorder.frame(&Grid::new(3, 3, 0), |_| [0; 3]).is_err());
+        let out = recorder.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (6, 4, 5));
+            let expected = gradient(count as f64 / 2.0);
+            assert_eq!(&frame.buffer[..3], &expected);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+    }
+}
@@ -0,0 +1,523 @@
+/// Structured records of runs and verification, for JSON and JSON Lines output
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, info, info_span};
+
+use crate::diagnostic;
+use crate::error::AocError;
+use crate::manifest::{Outcome, Status};
+use crate::solution::{Day, Part};
+use crate::source::Source;
+
+/// Serializable summary of an [`AocError`]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ErrorDetail {
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based, only known when the input the error came from is at hand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    pub message: String,
+    /// The error rendered against its input by [`diagnostic::render`], for text output
+    #[serde(skip)]
+    pub diagnostic: Option<String>,
+}
+
+impl From<&AocError> for ErrorDetail {
+    fn from(e: &AocError) -> Self {
+        let expected = match e.errors().first() {
+            Some(AocError::Parse { expected, .. }) => expected.clone(),
+            _ => None,
+        };
+        ErrorDetail {
+            kind: e.kind(),
+            line: e.line(),
+            expected,
+            message: e.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl ErrorDetail {
+    /// Summary of `e`, an error from parsing `content` read from `input`, pointing into it
+    pub fn in_input(e: &AocError, input: &str, content: &str) -> Self {
+        ErrorDetail {
+            column: diagnostic::locate(e, content).map(|(_, column)| column),
+            diagnostic: Some(diagnostic::render(e, input, content)),
+            ..ErrorDetail::from(e)
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// One part of one day, run on one input
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// Verification only: the answer from the manifest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Verification only: `pass`, `fail`, `error` or `missing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_ms: Option<f64>,
+    /// Wall time for the whole day: reading, parsing and solving all parts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wall_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetail>,
+}
+
+impl Record {
+    fn new(year: u32, day: u32, part: Part) -> Self {
+        Record {
+            year,
+            day,
+            part: part.number(),
+            input: None,
+            answer: None,
+            expected: None,
+            status: None,
+            parse_ms: None,
+            solve_ms: None,
+            wall_ms: None,
+            error: None,
+        }
+    }
+
+    pub fn from_outcome(outcome: &Outcome) -> Self {
+        let mut record = Record::new(outcome.year, outcome.day, outcome.part);
+        record.input = outcome.input.clone();
+        match &outcome.status {
+            Status::Pass => {
+                record.status = Some("pass");
+                record.answer = outcome.expected.clone();
+            }
+            Status::Fail { actual } => {
+                record.status = Some("fail");
+                record.answer = Some(actual.clone());
+            }
+            Status::Error(e) => {
+                record.status = Some("error");
+                record.error = Some(e.clone());
+            }
+            Status::Missing => record.status = Some("missing"),
+        }
+        if !matches!(outcome.status, Status::Missing) {
+            record.expected = outcome.expected.clone();
+            record.solve_ms = Some(millis(outcome.elapsed));
+        }
+        record
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Read, parse and solve the given parts of a day, timing each step. Failures are recorded in
+/// the returned records rather than returned as errors.
+pub fn run(day: &dyn Day, parts: &[Part], source: &Source) -> Vec<Record> {
+    let _span = info_span!("run", year = day.year(), day = day.day(), input = %source).entered();
+    let record = |part| Record {
+        input: Some(source.to_string()),
+        ..Record::new(day.year(), day.day(), part)
+    };
+    let start = Instant::now();
+    let content = match source.read() {
+        Ok(content) => content,
+        Err(e) => {
+            let parse_ms = Some(millis(start.elapsed()));
+            return failed(day, parts, &source.to_string(), &e)
+                .into_iter()
+                .map(|r| Record { parse_ms, ..r })
+                .collect();
+        }
+    };
+    let parse_start = Instant::now();
+    let (parsed, parse_time) = match day.parse(&content) {
+        Ok(parsed) => {
+            let parse_time = parse_start.elapsed();
+            debug!(ms = millis(parse_time), "parsed");
+            (parsed, parse_time)
+        }
+        Err(e) => {
+            let error = ErrorDetail::in_input(&e, &source.to_string(), &content);
+            return parts
+                .iter()
+                .map(|&part| Record {
+                    parse_ms: Some(millis(start.elapsed())),
+                    error: Some(error.clone()),
+                    ..record(part)
+                })
+                .collect();
+        }
+    };
+    parts
+        .iter()
+        .map(|&part| {
+            let _span = info_span!("part", part = part.number()).entered();
+            let start = Instant::now();
+            let result = parsed.solve(part);
+            let mut r = Record {
+                parse_ms: Some(millis(parse_time)),
+                solve_ms: Some(millis(start.elapsed())),
+                ..record(part)
+            };
+            match result {
+                Ok(answer) => {
+                    info!(ms = r.solve_ms, %answer, "solved");
+                    r.answer = Some(answer.to_string());
+                }
+                Err(e) => {
+                    debug!(error = %e, "failed");
+                    r.error = Some(ErrorDetail::from(&e));
+                }
+            }
+            r
+        })
+        .collect()
+}
+
+/// Like [`run`], but solves each part straight from a fresh reader on `source` with
+/// [`Day::stream`], without parsing the whole input first. Parts the day can't stream get an
+/// `unsupported` error record.
+pub fn run_streamed(day: &dyn Day, parts: &[Part], source: &Source) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|&part| {
+            let mut r = Record {
+                input: Some(source.to_string()),
+                ..Record::new(day.year(), day.day(), part)
+            };
+            let start = Instant::now();
+            let result = source.reader().map(|reader| {
+                let mut reader = LastLine::new(reader);
+                (day.stream(part, &mut reader), reader)
+            });
+            r.solve_ms = Some(millis(start.elapsed()));
+            match result {
+                Ok((Some(Ok(answer)), _)) => r.answer = Some(answer.to_string()),
+                Ok((Some(Err(e)), reader)) => {
+                    r.error = Some(match reader.excerpt(&e) {
+                        Some(content) => ErrorDetail::in_input(&e, &source.to_string(), &content),
+                        None => ErrorDetail::from(&e),
+                    })
+                }
+                Err(e) => r.error = Some(ErrorDetail::from(&e)),
+                Ok((None, _)) => {
+                    r.error = Some(ErrorDetail {
+                        kind: "unsupported",
+                        message: format!(
+                            "{} day {} part {} can't be streamed",
+                            day.year(),
+                            day.day(),
+                            part
+                        ),
+                        ..Default::default()
+                    })
+                }
+            }
+            r
+        })
+        .collect()
+}
+
+/// Reader that remembers the last line read through it, so that a parse error from a streamed
+/// input can be shown in context like one from a whole input
+struct LastLine<R> {
+    inner: R,
+    /// Number of the last line read, 1-based, counting one still being read
+    number: usize,
+    line: Vec<u8>,
+    /// Whether the last byte read ended a line
+    ended: bool,
+}
+
+impl<R: BufRead> LastLine<R> {
+    fn new(inner: R) -> Self {
+        LastLine {
+            inner,
+            number: 0,
+            line: Vec::new(),
+            ended: true,
+        }
+    }
+
+    /// Input for [`ErrorDetail::in_input`] if `e` is from the last line read: that line, with
+    /// blank lines in front of it so that it keeps its number
+    fn excerpt(&self, e: &AocError) -> Option<String> {
+        if e.line() != Some(self.number) {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.line);
+        Some(format!("{}{}", "\n".repeat(self.number - 1), line))
+    }
+}
+
+impl<R: BufRead> Read for LastLine<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LastLine<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            // the bytes being consumed are still buffered, so this doesn't read
+            if let Ok(buf) = self.inner.fill_buf() {
+                for &b in &buf[..amt.min(buf.len())] {
+                    if self.ended {
+                        self.line.clear();
+                        self.number += 1;
+                        self.ended = false;
+                    }
+                    match b {
+                        b'\n' => self.ended = true,
+                        b => self.line.push(b),
+                    }
+                }
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
+
+/// Like [`run`], but also records the day's wall time, and turns a panic into an error record
+/// instead of unwinding into the caller
+pub fn run_isolated(day: &dyn Day, parts: &[Part], source: &Source) -> Vec<Record> {
+    isolated(day, parts, source, || run(day, parts, source))
+}
+
+/// Run `f`, which returns records for `parts` of `day` on `source`, the way [`run_isolated`]
+/// runs [`run`]
+pub fn isolated<F>(day: &dyn Day, parts: &[Part], source: &Source, f: F) -> Vec<Record>
+where
+    F: FnOnce() -> Vec<Record>,
+{
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let wall_ms = Some(millis(start.elapsed()));
+    let records = match result {
+        Ok(records) => records,
+        Err(payload) => parts
+            .iter()
+            .map(|&part| Record {
+                input: Some(source.to_string()),
+                error: Some(ErrorDetail {
+                    kind: "panic",
+                    message: format!("panicked: {}", panic_message(payload.as_ref())),
+                    ..Default::default()
+                }),
+                ..Record::new(day.year(), day.day(), part)
+            })
+            .collect(),
+    };
+    records.into_iter().map(|r| Record { wall_ms, ..r }).collect()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown cause"
+    }
+}
+
+/// Run `job` for each of `days` on `jobs` threads.
+///
+/// `emit` is called on the calling thread with each day's records, in the order the days are
+/// given, as soon as that day and all days before it are done. Use [`run_isolated`] in `job` so
+/// that a day that panics is reported in its records instead of taking down its thread.
+pub fn run_many<J, F>(days: &[&dyn Day], jobs: usize, job: J, mut emit: F)
+where
+    J: Fn(&dyn Day) -> Vec<Record> + Sync,
+    F: FnMut(Vec<Record>),
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let tx = tx.clone();
+            let (next, job) = (&next, &job);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= days.len() || tx.send((i, job(days[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        // buffer days that finish early until everything before them is out
+        let mut done = BTreeMap::new();
+        let mut next_out = 0;
+        for (i, records) in rx {
+            done.insert(i, records);
+            while let Some(records) = done.remove(&next_out) {
+                emit(records);
+                next_out += 1;
+            }
+        }
+    });
+}
+
+/// Records for parts of a day that couldn't be run on `input` at all
+pub fn failed(day: &dyn Day, parts: &[Part], input: &str, e: &AocError) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|&part| Record {
+            input: Some(input.to_string()),
+            error: Some(ErrorDetail::from(e)),
+            ..Record::new(day.year(), day.day(), part)
+        })
+        .collect()
+}
+
+/// Render records as a pretty-printed JSON array
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records serialize to JSON")
+}
+
+/// Render records as JSON Lines, one compact object per line
+pub fn to_jsonl(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|r| serde_json::to_string(r).expect("records serialize to JSON") + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_records() {
+        let day = crate::solution::get(2021, 1).unwrap();
+        let records = run(day, &Part::ALL, &Source::Text("1\n2\n3\n".to_string()));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer.as_deref(), Some("2"));
+        assert_eq!(records[1].answer.as_deref(), Some("0"));
+        assert!(records[0].parse_ms.is_some() && records[0].solve_ms.is_some());
+
+        let records = run(day, &[Part::Two], &Source::Text("1\nx\n".to_string()));
+        let error = records[0].error.as_ref().unwrap();
+        assert_eq!((error.kind, error.line, error.column), ("parse", Some(2), Some(1)));
+        assert_eq!(error.expected.as_deref(), Some("a number"));
+        assert!(error.diagnostic.as_ref().unwrap().contains("2 | x\n  | ^ expected a number"));
+        assert_eq!(records[0].solve_ms, None);
+    }
+
+    #[test]
+    fn test_run_streamed() {
+        let day = crate::solution::get(2021, 1).unwrap();
+        let records = run_streamed(day, &Part::ALL, &Source::Text("1\n2\n3\n".to_string()));
+        assert_eq!(records[0].answer.as_deref(), Some("2"));
+        assert_eq!(records[1].answer.as_deref(), Some("0"));
+        assert!(records[0].solve_ms.is_some());
+
+        let day = crate::solution::get(2021, 3).unwrap();
+        let records = run_streamed(day, &[Part::Two], &Source::from_arg("inputs/2021/d3_test"));
+        let error = records[0].error.as_ref().unwrap();
+        assert_eq!(error.kind, "unsupported");
+        assert_eq!(error.message, "2021 day 3 part 2 can't be streamed");
+
+        let day = crate::solution::get(2021, 2).unwrap();
+        let source = Source::Text("forward 5\n\ndown 2\nsideways 3\nup 1\n".to_string());
+        let records = run_streamed(day, &[Part::One], &source);
+        let error = records[0].error.as_ref().unwrap();
+        assert_eq!((error.kind, error.line, error.column), ("parse", Some(4), Some(1)));
+        assert_eq!(error.expected.as_deref(), Some("`forward`, `up` or `down`"));
+        let diagnostic = error.diagnostic.as_ref().unwrap();
+        assert!(diagnostic.contains("4 | sideways 3\n  | ^^^^^^^^ expected"), "{}", diagnostic);
+    }
+
+    /// A day whose parser always panics
+    struct PanickingDay;
+
+    impl Day for PanickingDay {
+        fn year(&self) -> u32 {
+            2021
+        }
+
+        fn day(&self) -> u32 {
+            99
+        }
+
+        fn parse(&self, _content: &str) -> crate::error::Result<Box<dyn crate::solution::Parsed>> {
+            panic!("boom")
+        }
+    }
+
+    #[test]
+    fn test_run_many() {
+        let get = |n| crate::solution::get(2021, n).unwrap();
+        let text = || Source::Text("1\n2\n3\n4\n".to_string());
+        let days: Vec<&dyn Day> = vec![get(1), &PanickingDay, get(7), get(6)];
+        let mut emitted = Vec::new();
+        let job = |day: &dyn Day| run_isolated(day, &[Part::One], &text());
+        run_many(&days, 3, job, |records| emitted.extend(records));
+
+        let days: Vec<u32> = emitted.iter().map(|r| r.day).collect();
+        assert_eq!(days, vec![1, 99, 7, 6]);
+        assert_eq!(emitted[0].answer.as_deref(), Some("3"));
+        let error = emitted[1].error.as_ref().unwrap();
+        assert_eq!((error.kind, error.message.as_str()), ("panic", "panicked: boom"));
+        assert!(emitted[2].answer.is_some() && emitted[3].answer.is_some());
+        assert!(emitted.iter().all(|r| r.wall_ms.is_some()));
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let day = crate::solution::get(2021, 1).unwrap();
+        let records = run(day, &Part::ALL, &Source::Text("1\n".to_string()));
+        let jsonl = to_jsonl(&records);
+        let lines: Vec<serde_json::Value> =
+            jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0]["year"].as_u64(), lines[0]["day"].as_u64()), (Some(2021), Some(1)));
+        assert_eq!(lines[0]["input"], "<string>");
+        assert_eq!(lines[0]["answer"], "0");
+        assert_eq!(lines[1]["error"]["kind"], "unsolvable");
+        assert!(lines[1].get("answer").is_none());
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&records)).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,438 @@
+/// Scaffolding for a new day: module skeleton, cargo feature, registration, empty inputs and a
+/// manifest entry.
+///
+/// Each year's days live in `src/y<YEAR>/`, behind `y<YEAR>-d<DAY>` features, and are listed in
+/// the year's registry in `solution.rs`. The first day of a new year adds the year's module,
+/// feature and registry as well. Registering the day is enough for `aoc run`, `aoc verify`, the
+/// Criterion harness in `benches/days.rs` and the fuzz target to pick it up.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::error::{AocError, Result};
+use crate::solution::FIRST_YEAR;
+
+/// Module skeleton; every YYYY in it stands for the year and every capital N for the day number
+const TEMPLATE: &str = r#"/// AoC YYYY -- Day N
+/// https://adventofcode.com/YYYY/day/N
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::util;
+
+pub struct DayN;
+
+impl Solution for DayN {
+    const YEAR: u32 = YYYY;
+    const DAY: u32 = N;
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        dN_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        dN_part2(input)
+    }
+}
+
+pub fn parse_input(input_file: &str) -> Result<Vec<String>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<String>> {
+    Ok(util::numbered_lines(content).map(|(_, line)| line.to_string()).collect())
+}
+
+pub fn dN_part1(_lines: &[String]) -> Result<usize> {
+    Err(AocError::unsolvable("not solved yet"))
+}
+
+pub fn dN_part2(_lines: &[String]) -> Result<usize> {
+    Err(AocError::unsolvable("not solved yet"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_input_from_string("a\n\nb\n").unwrap(), vec!["a", "b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    #[ignore = "record the example answers in answers.toml first"]
+    fn test_dN_answers() {
+        manifest::assert_answers(YYYY, N);
+    }
+}
+"#;
+
+/// Source of a new, unsolved module for `day` of `year`
+pub fn module_source(year: u32, day: u32) -> String {
+    TEMPLATE.replace("YYYY", &year.to_string()).replace('N', &day.to_string())
+}
+
+/// Index of the first of the attributes, if any, above line `i`
+fn attributes_start(lines: &[String], mut i: usize) -> usize {
+    while i > 0 && lines[i - 1].trim_start().starts_with("#[") {
+        i -= 1;
+    }
+    i
+}
+
+/// Insert `new` among `lines` keeping the day numbers captured by `pattern` in order. Each
+/// existing line may have attributes above it, which move with it.
+fn insert_in_order(lines: &mut Vec<String>, pattern: &Regex, day: u32, new: &[String]) -> bool {
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| pattern.captures(l).map(|c| (i, c[1].parse().unwrap_or(0))))
+        .collect();
+    let at = match (days.iter().rev().find(|d| d.1 < day), days.first()) {
+        (Some(before), _) => before.0 + 1,
+        (None, Some(first)) => attributes_start(lines, first.0),
+        (None, None) => return false,
+    };
+    lines.splice(at..at, new.iter().cloned());
+    true
+}
+
+fn lines_of(text: &str) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
+/// Add `pub mod y<YEAR>;` to the crate root, keeping the year modules in order. A year that
+/// already has a module is left alone.
+pub fn add_year_module(lib: &str, year: u32) -> Result<String> {
+    let module = Regex::new(r"^pub mod y(\d+);$").unwrap();
+    let mut lines = lines_of(lib);
+    let new = format!("pub mod y{};", year);
+    if !lines.contains(&new) && !insert_in_order(&mut lines, &module, year, &[new]) {
+        return Err(AocError::parse(0, "no `pub mod yYYYY;` declarations"));
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add `pub mod d<DAY>;`, behind its feature, to the module of `year`, keeping the day modules
+/// in order. Without a module for the year yet, returns a new one.
+pub fn add_module(year_mod: Option<&str>, year: u32, day: u32) -> Result<String> {
+    let new = [
+        format!("#[cfg(feature = \"y{}-d{}\")]", year, day),
+        format!("pub mod d{};", day),
+    ];
+    let year_mod = match year_mod {
+        Some(year_mod) => year_mod,
+        None => {
+            let header = format!(
+                "/// Advent of Code {0}: one module per day, each built with its `y{0}-d<DAY>` \
+                 cargo feature",
+                year
+            );
+            return Ok(format!("{}\n{}\n{}\n", header, new[0], new[1]));
+        }
+    };
+    let module = Regex::new(r"^pub mod d(\d+);$").unwrap();
+    let mut lines = lines_of(year_mod);
+    if !insert_in_order(&mut lines, &module, day, &new) {
+        return Err(AocError::parse(0, "no `pub mod dN;` declarations"));
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add day `day` of `year`, behind its feature, to the year's registry in `solution.rs`, keeping
+/// it in order. A year without a registry gets one, which is added to `REGISTRY`.
+pub fn add_registration(solution: &str, year: u32, day: u32) -> Result<String> {
+    let entry = Regex::new(&format!(
+        r"^    &Registered::<crate::y{}::d(\d+)::Day\d+>::new\(\),$",
+        year
+    ))
+    .unwrap();
+    let registry =
+        Regex::new(r"(?m)^pub static REGISTRY: &\[&\[&dyn Day\]\] = &\[(.*)\];$").unwrap();
+    let mut lines = lines_of(solution);
+    let new = [
+        format!("    #[cfg(feature = \"y{}-d{}\")]", year, day),
+        format!("    &Registered::<crate::y{0}::d{1}::Day{1}>::new(),", year, day),
+    ];
+    let start = format!("pub static Y{}: &[&dyn Day] = &[", year);
+    if let Some(i) = lines.iter().position(|l| *l == start) {
+        if !insert_in_order(&mut lines, &entry, day, &new) {
+            lines.splice(i + 1..i + 1, new.iter().cloned());
+        }
+        return Ok(lines.join("\n") + "\n");
+    }
+
+    // a new year: its registry goes above `REGISTRY` and its docs, and into `REGISTRY`
+    let missing = || AocError::parse(0, "no `REGISTRY` in solution.rs");
+    let mut at = lines
+        .iter()
+        .position(|l| registry.is_match(l))
+        .ok_or_else(missing)?;
+    while at > 0 && lines[at - 1].starts_with("///") {
+        at -= 1;
+    }
+    let block = [
+        format!(
+            "/// {0}'s days, in order. Each is only built with its `y{0}-d<DAY>` cargo feature.",
+            year
+        ),
+        start,
+        new[0].clone(),
+        new[1].clone(),
+        "];".to_string(),
+        String::new(),
+    ];
+    lines.splice(at..at, block);
+    let solution = lines.join("\n") + "\n";
+
+    let caps = registry.captures(&solution).ok_or_else(missing)?;
+    let mut years: Vec<String> = caps[1].split(", ").map(String::from).collect();
+    years.push(format!("Y{}", year));
+    years.sort();
+    let list = format!("pub static REGISTRY: &[&[&dyn Day]] = &[{}];", years.join(", "));
+    Ok(registry.replace(&solution, list.as_str()).into_owned())
+}
+
+/// Add a `y<YEAR>-d<DAY>` feature to `Cargo.toml`, and add it to the `y<YEAR>` feature. A year
+/// without a feature gets one, which is added to the `days` feature.
+pub fn add_feature(cargo_toml: &str, year: u32, day: u32) -> Result<String> {
+    let feature = Regex::new(&format!(r"^y{}-d(\d+) = \[.*\]$", year)).unwrap();
+    let year_feature =
+        Regex::new(&format!(r#"(?m)^y{0} = \[\n((?:    "y{0}-d\d+",\n)*)\]$"#, year)).unwrap();
+    let days = Regex::new(r#"(?m)^days = \[((?:"y\d+", )*"y\d+")\]$"#).unwrap();
+    let missing = |what: &str| AocError::parse(0, format!("no {} in Cargo.toml", what));
+    let day_feature = format!("y{}-d{}", year, day);
+    // lines of a `y<YEAR>` feature with all of `days`, which are kept in order
+    let year_feature_lines = |mut days: Vec<u32>| {
+        days.sort_unstable();
+        let items = days.iter().map(|d| format!("    \"y{}-d{}\",\n", year, d));
+        format!("y{} = [\n{}]", year, items.collect::<String>())
+    };
+
+    if let Some(caps) = year_feature.captures(cargo_toml) {
+        let number = Regex::new(r"-d(\d+)").unwrap();
+        let mut all: Vec<u32> = number
+            .captures_iter(&caps[1])
+            .filter_map(|c| c[1].parse().ok())
+            .collect();
+        all.push(day);
+        let cargo_toml = year_feature.replace(cargo_toml, year_feature_lines(all).as_str());
+        let mut lines = lines_of(&cargo_toml);
+        if !insert_in_order(&mut lines, &feature, day, &[format!("{} = []", day_feature)]) {
+            return Err(missing("day features"));
+        }
+        return Ok(lines.join("\n") + "\n");
+    }
+
+    // a new year: its features go above `days`, which gets the year's
+    let caps = days.captures(cargo_toml).ok_or_else(|| missing("`days` feature"))?;
+    let mut all: Vec<String> = caps[1].split(", ").map(String::from).collect();
+    all.push(format!("\"y{}\"", year));
+    all.sort();
+    let new = format!(
+        "{} = []\n{}\ndays = [{}]",
+        day_feature,
+        year_feature_lines(vec![day]),
+        all.join(", ")
+    );
+    Ok(days.replace(cargo_toml, new.as_str()).into_owned())
+}
+
+/// Scaffold day `day` of `year` in the crate at `root`, adding the year's module, registry and
+/// feature along with its first day. Returns the files created or changed. Refuses to touch a
+/// day that already has a module; inputs that already exist are left alone.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    if year < FIRST_YEAR {
+        let msg = format!("no puzzles in {}", year);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+    }
+    if !(1..=25).contains(&day) {
+        let msg = format!("no puzzle for day {}", day);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+    }
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let module = year_dir.join(format!("d{}.rs", day));
+    if module.exists() {
+        let msg = format!("{} already exists", module.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, msg).into());
+    }
+    // edit everything in memory first, so a failure leaves the tree untouched
+    let year_mod = year_dir.join("mod.rs");
+    let lib = root.join("src").join("lib.rs");
+    let solution = root.join("src").join("solution.rs");
+    let cargo_toml = root.join("Cargo.toml");
+    let old_year_mod = match fs::read_to_string(&year_mod) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let new_year_mod = add_module(old_year_mod.as_deref(), year, day)?;
+    let old_lib = fs::read_to_string(&lib)?;
+    let new_lib = add_year_module(&old_lib, year)?;
+    let new_solution = add_registration(&fs::read_to_string(&solution)?, year, day)?;
+    let new_cargo_toml = add_feature(&fs::read_to_string(&cargo_toml)?, year, day)?;
+
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&module, module_source(year, day))?;
+    fs::write(&year_mod, new_year_mod)?;
+    let mut changed = vec![module, year_mod];
+    if new_lib != old_lib {
+        fs::write(&lib, new_lib)?;
+        changed.push(lib);
+    }
+    fs::write(&solution, new_solution)?;
+    fs::write(&cargo_toml, new_cargo_toml)?;
+    changed.extend([solution, cargo_toml]);
+
+    let inputs = root.join("inputs").join(year.to_string());
+    fs::create_dir_all(&inputs)?;
+    for input in [format!("d{}", day), format!("d{}_test", day)] {
+        let path = inputs.join(input);
+        if !path.exists() {
+            fs::write(&path, "")?;
+            changed.push(path);
+        }
+    }
+
+    let manifest = root.join(crate::manifest::DEFAULT_PATH);
+    let mut content = fs::read_to_string(&manifest).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push('\n');
+    }
+    content += &format!(
+        "[[answer]]\nyear = {0}\nday = {1}\ninput = \"inputs/{0}/d{1}_test\"\n\
+         # part1 =\n# part2 =\n",
+        year, day
+    );
+    fs::write(&manifest, content)?;
+    changed.push(manifest);
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(2021, 14);
+        assert!(source.starts_with("/// AoC 2021 -- Day 14\n"));
+        assert!(source.contains("/// https://adventofcode.com/2021/day/14\n"));
+        assert!(source.contains("pub struct Day14;"));
+        assert!(source.contains("const YEAR: u32 = 2021;\n    const DAY: u32 = 14;"));
+        assert!(source.contains("pub fn d14_part2(_lines: &[String])"));
+        assert!(source.contains("manifest::assert_answers(2021, 14);"));
+        assert!(!source.contains('N'));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("aoc_2021_scaffold_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2021")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod error;\npub mod y2021;\n").unwrap();
+        let year_mod = "/// 2021\n#[cfg(feature = \"y2021-d3\")]\npub mod d3;\n";
+        fs::write(root.join("src/y2021/mod.rs"), year_mod).unwrap();
+        let solution = r#"/// 2021's days
+pub static Y2021: &[&dyn Day] = &[
+    #[cfg(feature = "y2021-d3")]
+    &Registered::<crate::y2021::d3::Day3>::new(),
+];
+
+/// Every year's registry
+pub static REGISTRY: &[&[&dyn Day]] = &[Y2021];
+"#;
+        fs::write(root.join("src/solution.rs"), solution).unwrap();
+        let cargo_toml = "[features]\ny2021-d3 = [\"regex\"]\ny2021 = [\n    \"y2021-d3\",\n]\n\
+                          days = [\"y2021\"]\n";
+        fs::write(root.join("Cargo.toml"), cargo_toml).unwrap();
+
+        new_day(&root, 2021, 1).unwrap();
+        new_day(&root, 2021, 10).unwrap();
+        new_day(&root, 2022, 5).unwrap();
+        new_day(&root, 2022, 14).unwrap();
+        assert!(new_day(&root, 2021, 10).is_err());
+        assert!(new_day(&root, 2022, 5).is_err());
+        assert!(new_day(&root, 2021, 26).is_err());
+        assert!(new_day(&root, 21, 11).is_err());
+        assert!(root.join("src/y2022/d5.rs").exists());
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert_eq!(lib, "pub mod error;\npub mod y2021;\npub mod y2022;\n");
+        let year_mod = fs::read_to_string(root.join("src/y2021/mod.rs")).unwrap();
+        assert_eq!(
+            year_mod,
+            "/// 2021\n#[cfg(feature = \"y2021-d1\")]\npub mod d1;\n\
+             #[cfg(feature = \"y2021-d3\")]\npub mod d3;\n\
+             #[cfg(feature = \"y2021-d10\")]\npub mod d10;\n"
+        );
+        let year_mod = fs::read_to_string(root.join("src/y2022/mod.rs")).unwrap();
+        assert!(year_mod.starts_with("/// Advent of Code 2022: one module per day"));
+        assert!(year_mod.ends_with(
+            "\n#[cfg(feature = \"y2022-d5\")]\npub mod d5;\n\
+             #[cfg(feature = \"y2022-d14\")]\npub mod d14;\n"
+        ));
+        let solution = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        let expected = r#"/// 2021's days
+pub static Y2021: &[&dyn Day] = &[
+    #[cfg(feature = "y2021-d1")]
+    &Registered::<crate::y2021::d1::Day1>::new(),
+    #[cfg(feature = "y2021-d3")]
+    &Registered::<crate::y2021::d3::Day3>::new(),
+    #[cfg(feature = "y2021-d10")]
+    &Registered::<crate::y2021::d10::Day10>::new(),
+];
+
+/// 2022's days, in order. Each is only built with its `y2022-d<DAY>` cargo feature.
+pub static Y2022: &[&dyn Day] = &[
+    #[cfg(feature = "y2022-d5")]
+    &Registered::<crate::y2022::d5::Day5>::new(),
+    #[cfg(feature = "y2022-d14")]
+    &Registered::<crate::y2022::d14::Day14>::new(),
+];
+
+/// Every year's registry
+pub static REGISTRY: &[&[&dyn Day]] = &[Y2021, Y2022];
+"#;
+        assert_eq!(solution, expected);
+        let cargo_toml = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        let expected = r#"[features]
+y2021-d1 = []
+y2021-d3 = ["regex"]
+y2021-d10 = []
+y2021 = [
+    "y2021-d1",
+    "y2021-d3",
+    "y2021-d10",
+]
+y2022-d5 = []
+y2022-d14 = []
+y2022 = [
+    "y2022-d5",
+    "y2022-d14",
+]
+days = ["y2021", "y2022"]
+"#;
+        assert_eq!(cargo_toml, expected);
+        assert_eq!(fs::read_to_string(root.join("inputs/2021/d10_test")).unwrap(), "");
+        let manifest = crate::manifest::Manifest::load(root.join("answers.toml").to_str().unwrap());
+        let manifest = manifest.unwrap();
+        assert_eq!(manifest.entries_for(2021, 10).count(), 1);
+        assert_eq!(manifest.entries_for(2022, 14).count(), 1);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+/// Registry of every year's solutions, keyed by (year, day).
+///
+/// The traits the days implement live in `aoc_core::solution` and are re-exported here. Each
+/// year's days live in a module of their own, e.g. `src/y2021`, and are listed in a registry of
+/// their own, e.g. [`Y2021`]. [`REGISTRY`] chains the years together: `aoc new` adds a year's
+/// registry to it along with the year's first day, and a year kept in another crate on top of
+/// `aoc-core` is added by listing its registry there.
+pub use aoc_core::solution::*;
+
+/// 2021's days, in order. Each is only built with its `y2021-d<DAY>` cargo feature.
+pub static Y2021: &[&dyn Day] = &[
+    #[cfg(feature = "y2021-d1")]
+    &Registered::<crate::y2021::d1::Day1>::new(),
+    #[cfg(feature = "y2021-d2")]
+    &Registered::<crate::y2021::d2::Day2>::new(),
+    #[cfg(feature = "y2021-d3")]
+    &Registered::<crate::y2021::d3::Day3>::new(),
+    #[cfg(feature = "y2021-d4")]
+    &Registered::<crate::y2021::d4::Day4>::new(),
+    #[cfg(feature = "y2021-d5")]
+    &Registered::<crate::y2021::d5::Day5>::new(),
+    #[cfg(feature = "y2021-d6")]
+    &Registered::<crate::y2021::d6::Day6>::new(),
+    #[cfg(feature = "y2021-d7")]
+    &Registered::<crate::y2021::d7::Day7>::new(),
+    #[cfg(feature = "y2021-d8")]
+    &Registered::<crate::y2021::d8::Day8>::new(),
+    #[cfg(feature = "y2021-d9")]
+    &Registered::<crate::y2021::d9::Day9>::new(),
+    #[cfg(feature = "y2021-d10")]
+    &Registered::<crate::y2021::d10::Day10>::new(),
+    #[cfg(feature = "y2021-d11")]
+    &Registered::<crate::y2021::d11::Day11>::new(),
+    #[cfg(feature = "y2021-d12")]
+    &Registered::<crate::y2021::d12::Day12>::new(),
+    #[cfg(feature = "y2021-d13")]
+    &Registered::<crate::y2021::d13::Day13>::new(),
+];
+
+/// Every year's registry, oldest year first
+pub static REGISTRY: &[&[&dyn Day]] = &[Y2021];
+
+/// Iterate over all registered days, by year and then day
+pub fn days() -> impl Iterator<Item = &'static dyn Day> {
+    REGISTRY.iter().flat_map(|year| year.iter().copied())
+}
+
+/// Years with at least one registered day, in order
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = days().map(|d| d.year()).collect();
+    years.dedup();
+    years
+}
+
+/// The most recent year with a registered day, which a day given on its own belongs to
+pub fn latest_year() -> Option<u32> {
+    days().map(|d| d.year()).max()
+}
+
+/// Look up the solution for a given day of a given year
+pub fn get(year: u32, day: u32) -> Option<&'static dyn Day> {
+    days().find(|d| d.year() == year && d.day() == day)
+}
+
+/// Split command line numbers into a leading year, if the first is one (see [`FIRST_YEAR`]), and
+/// the days after it, so that `2022 5` and a bare `5` can both name a day
+pub fn split_year(numbers: &[u32]) -> (Option<u32>, &[u32]) {
+    match numbers.split_first() {
+        Some((&year, days)) if year >= FIRST_YEAR => (Some(year), days),
+        _ => (None, numbers),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "y2021-d1")]
+    use crate::answer::Answer;
+    #[cfg(feature = "y2021-d1")]
+    use crate::source::Source;
+    use proptest::prelude::*;
+
+    #[test]
+    #[cfg(feature = "y2021")]
+    fn test_registry_order() {
+        let registered: Vec<(u32, u32)> = days().map(|d| (d.year(), d.day())).collect();
+        let expected: Vec<(u32, u32)> = (1..=13).map(|day| (2021, day)).collect();
+        assert_eq!(registered[..13], expected);
+        assert!(registered.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(years()[0], 2021);
+        assert_eq!(latest_year(), registered.last().map(|d| d.0));
+    }
+
+    #[test]
+    #[cfg(feature = "y2021-d1")]
+    fn test_registry_solve() {
+        let day = get(2021, 1).unwrap();
+        let parsed = day.parse_source(&Source::from_arg("inputs/2021/d1_test")).unwrap();
+        assert_eq!(parsed.solve(Part::One).unwrap(), Answer::Int(7));
+        assert_eq!(parsed.solve(Part::Two).unwrap(), Answer::Int(5));
+        assert!(day.parse_source(&Source::from_arg("inputs/no_such_file")).is_err());
+        assert!(get(2021, 25).is_none());
+        assert!(get(2022, 1).is_none());
+    }
+
+    #[test]
+    fn test_split_year() {
+        assert_eq!(split_year(&[2022, 5]), (Some(2022), &[5][..]));
+        assert_eq!(split_year(&[2021]), (Some(2021), &[][..]));
+        assert_eq!(split_year(&[5]), (None, &[5][..]));
+        assert_eq!(split_year(&[14, 15]), (None, &[14, 15][..]));
+        assert_eq!(split_year(&[]), (None, &[][..]));
+    }
+
+    #[test]
+    #[cfg(feature = "y2021-d1")]
+    fn test_solution_sources() {
+        use crate::y2021::d1::Day1;
+
+        let example = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let from_str = Day1::parse(example).unwrap();
+        let from_reader = Day1::parse_reader(example.as_bytes()).unwrap();
+        let from_file = Day1::parse_file("inputs/2021/d1_test").unwrap();
+        assert_eq!(from_str, from_reader);
+        assert_eq!(from_str, from_file);
+        assert_eq!(Day1::part1(&from_str).unwrap(), 7);
+
+        let parsed = get(2021, 1).unwrap().parse(example).unwrap();
+        assert_eq!(parsed.solve(Part::Two).unwrap(), Answer::Int(5));
+    }
+
+    #[test]
+    #[cfg(feature = "y2021-d3")]
+    fn test_variants() {
+        let day = get(2021, 3).unwrap();
+        assert_eq!(day.variants(), vec![(Part::Two, "alt2")]);
+        let parsed = day.parse_source(&day.test_input()).unwrap();
+        let main = parsed.solve_variant(Part::Two, MAIN).unwrap().unwrap();
+        assert_eq!(parsed.solve_variant(Part::Two, "alt2").unwrap().unwrap(), main);
+        assert!(parsed.solve_variant(Part::One, "alt2").is_none());
+    }
+
+    proptest! {
+        /// No parser panics, whatever the input
+        #[test]
+        fn test_parsers_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let content = String::from_utf8_lossy(&bytes);
+            for day in days() {
+                let _ = day.parse(&content);
+            }
+        }
+
+        /// Inputs made of puzzle-like fragments reach deeper into the parsers than random bytes
+        #[test]
+        fn test_parsers_never_panic_on_fragments(
+            fragments in prop::collection::vec(
+                prop_oneof![
+                    "[0-9]{1,25}",
+                    "[a-gA-Z]{1,8}",
+                    Just("start".to_string()),
+                    Just("end".to_string()),
+                    "fold along [xyz]=[0-9]{0,6}",
+                    "(forward|up|down) -?[0-9]{1,4}",
+                    "[()\\[\\]{}<>]{1,10}",
+                    "[ ,|\n-]|->",
+                ],
+                0..64,
+            )
+        ) {
+            let content = fragments.concat();
+            for day in days() {
+                let _ = day.parse(&content);
+            }
+        }
+    }
+}
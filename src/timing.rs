@@ -0,0 +1,126 @@
+/// Timing of each solution's parse and solve steps, over repeated runs
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::solution::{Day, Part};
+
+/// The step of a solution being timed
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Summary of repeated measurements
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarize a set of samples, or `None` if there are none
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Some(Stats {
+            runs: n,
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Run `f` `runs` times (at least once), returning the last result along with the timings
+pub(crate) fn repeat<T, F>(runs: usize, mut f: F) -> Result<(T, Stats)>
+where
+    F: FnMut() -> Result<T>,
+{
+    let mut samples = Vec::with_capacity(runs);
+    let mut last = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        last = Some(result);
+    }
+    Ok((last.unwrap(), Stats::from_samples(samples).unwrap()))
+}
+
+/// Time parsing `content` and solving each of `parts`, `runs` times each. Reading the input isn't
+/// included.
+pub fn time_day(day: &dyn Day, content: &str, parts: &[Part], runs: usize) -> Result<Vec<Timing>> {
+    let (parsed, stats) = repeat(runs, || day.parse(content))?;
+    let mut timings = vec![Timing {
+        year: day.year(),
+        day: day.day(),
+        stage: Stage::Parse,
+        stats,
+    }];
+    for &part in parts {
+        let (_answer, stats) = repeat(runs, || parsed.solve(part))?;
+        timings.push(Timing {
+            year: day.year(),
+            day: day.day(),
+            stage: Stage::Solve(part),
+            stats,
+        });
+    }
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(Stats::from_samples(vec![]), None);
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!((stats.runs, stats.median), (4, ms(3)));
+    }
+
+    #[test]
+    fn test_time_day() {
+        let day = crate::solution::get(2021, 1).unwrap();
+        let content = crate::util::read_to_string("inputs/2021/d1_test").unwrap();
+        let timings = time_day(day, &content, &Part::ALL, 3).unwrap();
+        let stages: Vec<Stage> = timings.iter().map(|t| t.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Solve(Part::One), Stage::Solve(Part::Two)]);
+        assert!(timings.iter().all(|t| t.stats.runs == 3 && t.stats.min <= t.stats.max));
+
+        assert!(time_day(day, "1\nx\n", &Part::ALL, 3).is_err());
+    }
+}
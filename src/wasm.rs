@@ -0,0 +1,67 @@
+/// Browser bindings: solve any registered day of any year from an in-memory string, for the
+/// playground in `www/index.html`.
+///
+/// Everything here goes through the days' string parsers, so nothing touches the file system or
+/// the clock, neither of which exists in the browser; the page does its own timing. Build with
+///
+/// ```text
+/// $ wasm-pack build --target web --out-dir www/pkg -- --features wasm
+/// ```
+use wasm_bindgen::prelude::*;
+
+use crate::solution::{self, Part};
+
+/// Years with a solution, in order
+#[wasm_bindgen]
+pub fn years() -> Vec<u32> {
+    solution::years()
+}
+
+/// Days of `year` with a solution, in order
+#[wasm_bindgen]
+pub fn days(year: u32) -> Vec<u32> {
+    solution::days().filter(|d| d.year() == year).map(|d| d.day()).collect()
+}
+
+/// Solve part 1 or 2 of `day` of `year` on `input`. Failures are thrown as an `Error` with the
+/// message.
+#[wasm_bindgen]
+pub fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, JsError> {
+    solve_string(year, day, part, input).map_err(|msg| JsError::new(&msg))
+}
+
+/// [`solve`] without the JavaScript error type, so that it can be tested natively
+fn solve_string(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: &str,
+) -> std::result::Result<String, String> {
+    let solution = solution::get(year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+    let part = Part::from_number(part).ok_or_else(|| format!("no part {}", part))?;
+    solution
+        .parse(input)
+        .and_then(|parsed| parsed.solve(part))
+        .map(|answer| answer.to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_string() {
+        let example = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(solve_string(2021, 1, 1, example).unwrap(), "7");
+        assert_eq!(solve_string(2021, 1, 2, example).unwrap(), "5");
+        assert_eq!(solve_string(2021, 25, 1, example).unwrap_err(), "no solution for 2021 day 25");
+        assert_eq!(solve_string(2022, 1, 1, example).unwrap_err(), "no solution for 2022 day 1");
+        assert_eq!(solve_string(2021, 1, 3, example).unwrap_err(), "no part 3");
+        assert!(solve_string(2021, 1, 1, "x").unwrap_err().starts_with("parse error"));
+        assert_eq!(years(), vec![2021]);
+        assert_eq!(days(2021)[..13], (1..=13).collect::<Vec<u32>>());
+        assert!(days(2022).is_empty());
+    }
+}
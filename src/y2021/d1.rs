@@ -0,0 +1,162 @@
+/// AoC 2021 -- Day 1
+/// https://adventofcode.com/2021/day/1
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::{Part, Solution};
+use crate::util;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 1;
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d1_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        d1_part2(input)
+    }
+
+    fn stream<R: BufRead>(part: Part, reader: R) -> Option<Result<Answer>> {
+        let answer = match part {
+            Part::One => d1_part1_streaming(reader),
+            Part::Two => d1_part2_streaming(reader),
+        };
+        Some(answer.map(Answer::from))
+    }
+}
+
+pub fn main() -> Result<()> {
+    let depths = parse_input("inputs/2021/d1")?;
+
+    let d1_part1_soln = d1_part1(&depths)?;
+    println!("Day 1, part 1 solution: {}", d1_part1_soln);
+
+    let d1_part2_soln = d1_part2(&depths)?;
+    println!("Day 1, part 2 solution: {}", d1_part2_soln);
+    Ok(())
+}
+
+pub fn parse_input(input_file: &str) -> Result<Vec<i64>> {
+    util::read_ints(input_file)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<i64>> {
+    util::parse_ints(content)
+}
+
+pub fn d1_part1(es: &[i64]) -> Result<usize> {
+    if es.is_empty() {
+        return Err(AocError::unsolvable("no depth measurements"));
+    }
+    let len = es.len();
+
+    let preds = &es[0..len];
+    let succs = &es[1..];
+    Ok(preds
+        .iter()
+        .zip(succs)
+        .filter(|(&p, &s)| s > p)
+        .count())
+}
+
+pub fn d1_part2(es: &[i64]) -> Result<usize> {
+    if es.len() < 3 {
+        return Err(AocError::unsolvable("fewer than 3 depth measurements"));
+    }
+    let windows: Vec<&[i64]> = es.windows(3).collect();
+    let len = windows.len();
+    let preds = &windows[0..len];
+    let succs = &windows[1..];
+    Ok(preds
+        .iter()
+        .zip(succs)
+        .filter(|(&pw, &sw)| sw[2] > pw[0])
+        .count())
+}
+
+/// Count the depths read from `reader` that are larger than the one `lag` measurements before,
+/// remembering only the last `lag` depths. Also returns the number of depths read.
+fn count_increases<R: BufRead>(reader: R, lag: usize) -> Result<(usize, usize)> {
+    let mut recent = vec![0; lag];
+    let (mut count, mut seen) = (0, 0);
+    util::for_each_line(reader, |n, line| {
+        let depth = util::parse_num::<i64>(line, n)?;
+        let slot = seen % lag;
+        if seen >= lag && depth > recent[slot] {
+            count += 1;
+        }
+        recent[slot] = depth;
+        seen += 1;
+        Ok(())
+    })?;
+    Ok((count, seen))
+}
+
+/// [`d1_part1`] straight from a reader, in constant memory
+pub fn d1_part1_streaming<R: BufRead>(reader: R) -> Result<usize> {
+    match count_increases(reader, 1)? {
+        (_, 0) => Err(AocError::unsolvable("no depth measurements")),
+        (count, _) => Ok(count),
+    }
+}
+
+/// [`d1_part2`] straight from a reader, in constant memory. Consecutive windows share their middle
+/// two depths, so comparing their sums is comparing depths three measurements apart.
+pub fn d1_part2_streaming<R: BufRead>(reader: R) -> Result<usize> {
+    match count_increases(reader, 3)? {
+        (_, seen) if seen < 3 => Err(AocError::unsolvable("fewer than 3 depth measurements")),
+        (count, _) => Ok(count),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d1_answers() {
+        manifest::assert_answers(2021, 1);
+    }
+
+    #[test]
+    fn test_d1_errors() {
+        assert!(matches!(d1_part1(&[]), Err(AocError::Unsolvable(_))));
+        assert!(matches!(d1_part2(&[1, 2]), Err(AocError::Unsolvable(_))));
+        assert!(matches!(d1_part1_streaming("".as_bytes()), Err(AocError::Unsolvable(_))));
+        assert!(matches!(d1_part2_streaming("1\n2".as_bytes()), Err(AocError::Unsolvable(_))));
+        let result = d1_part1_streaming("1\nx".as_bytes());
+        assert!(matches!(result, Err(AocError::Parse { line: 2, .. })));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(depths in prop::collection::vec(any::<i64>(), 0..50)) {
+            let text: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), depths);
+        }
+
+        #[test]
+        fn test_streaming(depths in prop::collection::vec(-100i64..100, 3..50)) {
+            let text: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+            let (part1, part2) = (d1_part1(&depths).unwrap(), d1_part2(&depths).unwrap());
+            prop_assert_eq!(d1_part1_streaming(text.as_bytes()).unwrap(), part1);
+            prop_assert_eq!(d1_part2_streaming(text.as_bytes()).unwrap(), part2);
+        }
+    }
+}
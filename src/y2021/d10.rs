@@ -0,0 +1,254 @@
+/// AoC 2021 -- Day 10
+/// https://adventofcode.com/2021/day/10
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::{Part, Solution};
+use crate::util;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 10;
+    type Input = Vec<Line>;
+    type Answer1 = Score;
+    type Answer2 = Score;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Score> {
+        d10_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Score> {
+        d10_part2(input)
+    }
+
+    fn stream<R: BufRead>(part: Part, reader: R) -> Option<Result<Answer>> {
+        let answer = match part {
+            Part::One => d10_part1_streaming(reader),
+            Part::Two => d10_part2_streaming(reader),
+        };
+        Some(answer.map(Answer::from))
+    }
+}
+
+type Line = Vec<char>;
+type Stack = Vec<char>;
+type Score = u64;
+
+pub fn parse_input(input_file: &str) -> Result<Vec<Line>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<Line>> {
+    util::parse_lines(content, parse_line)
+}
+
+/// Parse line number `n`, a string of chunk delimiters
+fn parse_line(n: usize, s: &str) -> Result<Line> {
+    match s.char_indices().find(|&(_, c)| !is_chunk_start(c) && !is_chunk_end(c)) {
+        Some((i, c)) => Err(AocError::parse(n, format!("invalid chunk delimiter: {:?}", c))
+            .at(s, &s[i..i + c.len_utf8()])
+            .expected("one of `()[]{}<>`")),
+        None => Ok(s.chars().collect()),
+    }
+}
+
+fn is_chunk_start(c: char) -> bool {
+    matches!(c, '(' | '[' | '<' | '{')
+}
+
+fn is_chunk_end(c: char) -> bool {
+    matches!(c, ')' | ']' | '>' | '}')
+}
+
+/// Return the chunk-end matching chunk-start `c`, or None if `c` isn't a chunk-start
+fn chunk_match(c: char) -> Option<char> {
+    match c {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '<' => Some('>'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn part1_score(c: char) -> Result<Score> {
+    match c {
+        ')' => Ok(3),
+        ']' => Ok(57),
+        '}' => Ok(1197),
+        '>' => Ok(25137),
+        _ => Err(AocError::invalid_state(format!("woah! bad chunk-end dude: {:?}", c))),
+    }
+}
+
+fn part2_score(c: char) -> Result<Score> {
+    match c {
+        ')' => Ok(1),
+        ']' => Ok(2),
+        '}' => Ok(3),
+        '>' => Ok(4),
+        _ => Err(AocError::invalid_state(format!("woah! bad chunk-end dude: {:?}", c))),
+    }
+}
+
+/// If line is corrupt, return Some(first invalid chunk closer) and the remaining stack, if not
+/// corrupt, return None. A chunk closer with no open chunk also counts as corrupt.
+pub fn corrupt_line(line: &[char]) -> (Option<char>, Stack) {
+    let mut stack: Stack = Vec::new();
+    for &c in line {
+        if is_chunk_start(c) {
+            stack.push(c);
+        } else {
+            let top = stack.last().copied();
+            if top.and_then(chunk_match) == Some(c) {
+                stack.pop();
+            } else {
+                // invalid chunk-end
+                // return the chunk-end we expected to find
+                return (Some(c), stack);
+            }
+        }
+    }
+    (None, stack)
+}
+
+pub fn d10_part1(lines: &[Line]) -> Result<Score> {
+    lines
+        .iter()
+        .filter_map(|l| corrupt_line(l).0)
+        .map(part1_score)
+        .sum()
+}
+
+pub fn d10_part2(lines: &[Line]) -> Result<Score> {
+    let lc_scores: Vec<Score> = lines
+        .iter()
+        .map(|l| corrupt_line(l))
+        // filter out corrupted lines
+        .filter(|(c, _)| c.is_none())
+        .map(|(_, stack)| completion_score(stack))
+        .collect::<Result<_>>()?;
+    middle_score(lc_scores)
+}
+
+/// Score of the chunk-ends that complete an incomplete line with open chunks `stack`
+fn completion_score(stack: Stack) -> Result<Score> {
+    // reverse each chunk delimiter, then reverse the stack
+    stack.into_iter().rev().try_fold(0, |acc: Score, c| {
+        let end = chunk_match(c)
+            .ok_or_else(|| AocError::invalid_state(format!("woah! bad chunk dude: {:?}", c)))?;
+        let score = part2_score(end)?;
+        acc.checked_mul(5)
+            .and_then(|acc| acc.checked_add(score))
+            .ok_or_else(|| AocError::unsolvable("the completion score overflows a u64"))
+    })
+}
+
+fn middle_score(mut lc_scores: Vec<Score>) -> Result<Score> {
+    lc_scores.sort_unstable();
+    // return the middle score, which only exists if lc_scores is odd
+    if lc_scores.len() % 2 != 1 {
+        return Err(AocError::unsolvable(format!(
+            "expected an odd number of incomplete lines, found {}",
+            lc_scores.len()
+        )));
+    }
+    Ok(lc_scores[lc_scores.len() / 2])
+}
+
+/// [`d10_part1`] straight from a reader, scoring each line as it's read
+pub fn d10_part1_streaming<R: BufRead>(reader: R) -> Result<Score> {
+    let mut total = 0;
+    util::for_each_line(reader, |n, line| {
+        if let (Some(c), _) = corrupt_line(&parse_line(n, line)?) {
+            total += part1_score(c)?;
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+/// [`d10_part2`] straight from a reader. Only the completion score of each incomplete line is
+/// kept for finding the middle one, rather than the lines themselves.
+pub fn d10_part2_streaming<R: BufRead>(reader: R) -> Result<Score> {
+    let mut lc_scores = Vec::new();
+    util::for_each_line(reader, |n, line| {
+        if let (None, stack) = corrupt_line(&parse_line(n, line)?) {
+            lc_scores.push(completion_score(stack)?);
+        }
+        Ok(())
+    })?;
+    middle_score(lc_scores)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    fn test_parser() {
+        let test_lines = parse_input("inputs/2021/d10_test").unwrap();
+        assert_eq!(test_lines.len(), 10);
+        let lines = parse_input("inputs/2021/d10").unwrap();
+        assert_eq!(lines.len(), 106);
+    }
+
+    #[test]
+    fn test_corrupt_line_ex() {
+        let line1: Line = "{([(<{}[<>[]}>{[]{[(<()>".chars().collect();
+        assert_eq!(corrupt_line(&line1).0, Some('}'));
+        let line2: Line = "[[<[([]))<([[{}[[()]]]".chars().collect();
+        assert_eq!(corrupt_line(&line2).0, Some(')'));
+        let line3: Line = "[[<[([])<([[{}[[()]]]".chars().collect();
+        assert_eq!(corrupt_line(&line3).0, None);
+        let line4: Line = "()]".chars().collect();
+        assert_eq!(corrupt_line(&line4).0, Some(']'));
+    }
+
+    #[test]
+    fn test_d10_streaming() {
+        for input_file in ["inputs/2021/d10_test", "inputs/2021/d10"] {
+            let lines = parse_input(input_file).unwrap();
+            let file = || std::io::BufReader::new(std::fs::File::open(input_file).unwrap());
+            assert_eq!(d10_part1_streaming(file()).unwrap(), d10_part1(&lines).unwrap());
+            assert_eq!(d10_part2_streaming(file()).unwrap(), d10_part2(&lines).unwrap());
+        }
+        let result = d10_part1_streaming("()\n(x)\n".as_bytes());
+        assert!(matches!(result, Err(AocError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_d10_overflow() {
+        // 5^28 no longer fits in a u64
+        let lines = parse_input_from_string(&"(".repeat(30)).unwrap();
+        assert!(matches!(d10_part2(&lines), Err(AocError::Unsolvable(_))));
+        let result = d10_part2_streaming("(".repeat(30).as_bytes());
+        assert!(matches!(result, Err(AocError::Unsolvable(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d10_answers() {
+        manifest::assert_answers(2021, 10);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(lines in prop::collection::vec("[()\\[\\]{}<>]{1,40}", 0..20)) {
+            let text = lines.join("\n");
+            let expected: Vec<Line> = lines.iter().map(|l| l.chars().collect()).collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), expected);
+        }
+    }
+}
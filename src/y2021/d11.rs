@@ -0,0 +1,324 @@
+/// AoC 2021 -- Day 11
+/// https://adventofcode.com/2021/day/11
+use std::collections::HashSet;
+
+use tracing::{debug, debug_span, trace};
+
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::{grid::Grid, util};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 11;
+    type Input = OctoGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d11_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        d11_part2(input)
+    }
+}
+
+pub type Energy = u32;
+pub type OctoGrid = Grid<Energy>;
+const MAX_ENERGY: u32 = 9;
+
+pub fn parse_input(input_file: &str) -> Result<OctoGrid> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<OctoGrid> {
+    let rows: Vec<Vec<Energy>> = util::parse_lines(content, |n, line| {
+        line.char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10).ok_or_else(|| {
+                    AocError::parse(n, format!("invalid energy: {:?}", c))
+                        .at(line, &line[i..i + c.len_utf8()])
+                        .expected("a digit")
+                })
+            })
+            .collect()
+    })?;
+    Grid::from_rows(rows).map_err(|e| AocError::parse(0, e))
+}
+
+/// Add one energy to all octopods, do not flash
+fn inc_energy(grid: &mut OctoGrid) {
+    for energy in grid.iter_mut() {
+        *energy += 1;
+    }
+}
+
+const ADJACENCIES: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Flash the octopod at (row, col): adds 1 energy to all adjecent (incl. diagonals)
+/// octopods and resets center to 0.
+fn flash(grid: &mut OctoGrid, row: usize, col: usize) {
+    // increment adjacent octopods
+    for (dr, dc) in ADJACENCIES {
+        let rr = (row as i32) + dr;
+        let cc = (col as i32) + dc;
+        if let Some(&v) = grid.geti32(rr, cc) {
+            grid.seti32(rr, cc, v + 1);
+        }
+    }
+}
+
+/// Find octopods whose energy level is beyond max.
+///
+/// Ignore octopods at positions in the `ignores` list.
+fn find_limit_break_octopus(grid: &OctoGrid, ignores: &HashSet<(usize, usize)>) -> Option<(usize, usize)> {
+    grid.iter_pos_val()
+        .filter(|(r, c, &v)|
+            !ignores.contains(&(*r, *c)) && v > MAX_ENERGY)
+        .map(|(r, c, _)| (r, c))
+        .next()
+}
+
+/// Simulate a single step of the OctoGrid in-place.
+///
+/// Return the number of flashes that occured during the step.
+fn step(grid: &mut OctoGrid) -> usize {
+    // 1. increment octopod energies
+    inc_energy(grid);
+
+    // 2. simulate flashes
+    let mut flashers: HashSet<(usize, usize)> = HashSet::new();
+    while let Some((r, c)) = find_limit_break_octopus(grid, &flashers) {
+        trace!(row = r, col = c, "flash");
+        flash(grid, r, c);
+        flashers.insert((r, c));
+    }
+
+    // 3. reset the flashers
+    let nflashes = flashers.len();
+    for (r, c) in flashers {
+        grid.set(r, c, 0);
+    }
+    nflashes
+}
+
+/// The grid before and after each of `nsteps` steps, e.g. for rendering
+pub fn frames(grid: &OctoGrid, nsteps: usize) -> Vec<OctoGrid> {
+    let mut grid = grid.clone();
+    let mut frames = vec![grid.clone()];
+    for _ in 0..nsteps {
+        step(&mut grid);
+        frames.push(grid.clone());
+    }
+    frames
+}
+
+/// Simulate `nsteps` of the OctoGrid.
+///
+/// Return the final grid and the number of flashes that occured.
+/// Return the first syncronization step number, or 0 if there was no sync
+///
+/// Each step is traced at debug level, with the grid before it at trace level, along with each
+/// flash.
+pub fn simulate(grid: &OctoGrid, nsteps: usize) -> (OctoGrid, usize, usize) {
+    let _span = debug_span!("simulate", nsteps).entered();
+    let mut grid = grid.clone();
+    let mut nflashes: usize = 0;
+    let mut first_sync: usize = 0;
+    for s in 0..nsteps {
+        let _span = debug_span!("step", step = s + 1).entered();
+        trace!(grid = %grid, "before step");
+        let step_flashes = step(&mut grid);
+        debug!(flashes = step_flashes, "stepped");
+        nflashes += step_flashes;
+        if first_sync == 0 && step_flashes == grid.rows * grid.cols {
+            debug!("synchronized");
+            first_sync = s + 1;
+        }
+    }
+    trace!(grid = %grid, "final grid");
+    (grid, nflashes, first_sync)
+}
+
+/// Total number of flashes after 100 steps
+pub fn d11_part1(grid: &OctoGrid) -> Result<usize> {
+    Ok(simulate(grid, 100).1)
+}
+
+/// Give up looking for a synchronized flash after this many steps
+const MAX_SYNC_STEPS: usize = 100_000;
+
+/// First step during which all octopods flash at once
+pub fn d11_part2(grid: &OctoGrid) -> Result<usize> {
+    let mut grid = grid.clone();
+    for s in 1..=MAX_SYNC_STEPS {
+        if step(&mut grid) == grid.rows * grid.cols {
+            return Ok(s);
+        }
+    }
+    Err(AocError::unsolvable(format!(
+        "octopods did not synchronize within {} steps",
+        MAX_SYNC_STEPS
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    fn test_parser() {
+        let test_grid = parse_input("inputs/2021/d11_test").unwrap();
+        assert_eq!(test_grid.rows, 10);
+        assert_eq!(test_grid.cols, 10);
+
+        let grid = parse_input("inputs/2021/d11").unwrap();
+        assert_eq!(grid.rows, 10);
+        assert_eq!(grid.cols, 10);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        assert!(matches!(
+            parse_input_from_string("123\n4x6"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(parse_input_from_string("123\n45").is_err());
+    }
+
+    #[test]
+    fn test_inc_energy() {
+        let content = vec![vec![0, 1, 0], vec![2, 3, 4]];
+        let mut grid: OctoGrid = Grid::from_rows(content).unwrap();
+        assert_eq!(grid.iter().sum::<u32>(), 10);
+
+        inc_energy(&mut grid);
+        assert_eq!(grid.get(0, 0), Some(&1));
+        assert_eq!(grid.get(0, 1), Some(&2));
+        assert_eq!(grid.iter().sum::<u32>(), 16);
+    }
+
+    #[test]
+    fn test_flash() {
+        let content = vec![vec![9, 1, 0, 1], vec![2, 3, 4, 0]];
+        let mut grid: OctoGrid = Grid::from_rows(content).unwrap();
+        flash(&mut grid, 0, 0);
+        assert_eq!(grid.get(0, 0), Some(&9));  // flash doesn't change to center
+        assert_eq!(grid.get(0, 1), Some(&2));
+        assert_eq!(grid.get(0, 2), Some(&0));
+        assert_eq!(grid.get(1, 0), Some(&3));
+        assert_eq!(grid.get(1, 1), Some(&4));
+        assert_eq!(grid.get(1, 2), Some(&4));
+    }
+
+    #[test]
+    fn test_find_limit_break() {
+        let content = vec![vec![0, 1, 0, 1], vec![2, 9, 4, 0]];
+        let mut grid: OctoGrid = Grid::from_rows(content).unwrap();
+        assert!(find_limit_break_octopus(&grid, &HashSet::new()).is_none());
+
+        inc_energy(&mut grid);
+        let limit_octo = find_limit_break_octopus(&grid, &HashSet::new()).unwrap();
+        assert_eq!(limit_octo, (1, 1));
+        flash(&mut grid, limit_octo.0, limit_octo.1);
+        assert_eq!(grid.get(limit_octo.0, limit_octo.1), Some(&10));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let grid = parse_input_from_string("
+            0101
+            1090
+            0101
+        ").unwrap();
+        let (grid_1, nf1, _fs) = simulate(&grid, 1);
+        assert_eq!(nf1, 1);
+        assert_eq!(grid_1.get(0, 0), Some(&1));  // not part of the single flash
+        assert_eq!(grid_1.get(0, 2), Some(&2));  // adjacent to the single flash
+        assert_eq!(grid_1.get(0, 3), Some(&3));  // adjacent to the single flash
+        assert_eq!(grid_1.get(1, 2), Some(&0));  // center of the single flash
+
+        let (grid_2, nf2, _fs) = simulate(&grid, 2);
+        assert_eq!(nf2, 1);
+        assert_eq!(grid_2.get(0, 0), Some(&2));  // not part of the single flash
+        assert_eq!(grid_2.get(0, 2), Some(&3));  // adjacent to the single flash
+        assert_eq!(grid_2.get(0, 3), Some(&4));  // adjacent to the single flash
+        assert_eq!(grid_2.get(1, 2), Some(&1));  // center of the single flash
+    }
+
+    #[test]
+    fn test_simulate_small_example() {
+        let grid = parse_input_from_string("
+            11111
+            19991
+            19191
+            19991
+            11111
+        ").unwrap();
+        let grid_1 = parse_input_from_string("
+            34543
+            40004
+            50005
+            40004
+            34543
+        ").unwrap();
+        let grid_2 = parse_input_from_string("
+            45654
+            51115
+            61116
+            51115
+            45654
+        ").unwrap();
+        assert_eq!(grid, simulate(&grid, 0).0);
+        assert_eq!(grid_1, simulate(&grid, 1).0);
+        assert_eq!(grid_2, simulate(&grid, 2).0);
+    }
+
+    #[test]
+    fn test_simulate_10_steps() {
+        let grid = parse_input("inputs/2021/d11_test").unwrap();
+        let (_final_grid_10, nf, _fs) = simulate(&grid, 10);
+        assert_eq!(nf, 204);
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d11_answers() {
+        manifest::assert_answers(2021, 11);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            rows in (1usize..12).prop_flat_map(|w| {
+                prop::collection::vec(prop::collection::vec(0u32..10, w), 1..12)
+            })
+        ) {
+            let text: String = rows
+                .iter()
+                .map(|r| r.iter().map(|v| v.to_string()).collect::<String>() + "\n")
+                .collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), Grid::from_rows(rows).unwrap());
+        }
+    }
+}
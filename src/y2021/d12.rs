@@ -4,9 +4,34 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 
+use tracing::{debug, debug_span, trace, warn};
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
 use crate::util;
 
-const DEBUG: bool = false;
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 12;
+    type Input = Graph;
+    type Answer1 = Paths;
+    type Answer2 = Paths;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Paths> {
+        paths(input, 1).map(Paths)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Paths> {
+        paths(input, 2).map(Paths)
+    }
+}
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Node(String);
@@ -28,7 +53,16 @@ impl fmt::Display for Node {
 }
 
 /// Finite path through a graph
-type Path = Vec<Node>;
+pub type Path = Vec<Node>;
+
+/// All the paths from start to end, which answer the puzzle by how many there are
+pub struct Paths(pub Vec<Path>);
+
+impl From<Paths> for Answer {
+    fn from(paths: Paths) -> Answer {
+        paths.0.len().into()
+    }
+}
 type PathSlice<'a> = &'a [Node];
 
 pub fn fmt_path(v: PathSlice) -> String {
@@ -41,7 +75,7 @@ pub struct Graph {
     neighbors: HashMap<Node, Vec<Node>>
 }
 
-const MAX_STEPS: usize = 200000;
+const MAX_STEPS: usize = 2_000_000;
 
 impl Graph {
     /// Enumerate all paths in ``self`` from start to end that traverse each small node at most
@@ -85,12 +119,20 @@ impl Graph {
     /// - start: starting node
     /// - end: ending node
     /// - max_ssn: maximum number of times that a single small node is allowed to appear in
-    ///     each result path. Other small nodes are limited to 1 visit, as is the start node
-    ///     and the end node.
-    pub fn list_paths(&self, start: Node, end: Node, max_ssn: usize) -> Vec<Path> {
+    ///   each result path. Other small nodes are limited to 1 visit, as is the start node
+    ///   and the end node.
+    ///
+    /// Returns an error if `start` isn't in the graph, or if the search runs out of fuel before
+    /// all paths are found.
+    pub fn list_paths(&self, start: Node, end: Node, max_ssn: usize) -> Result<Vec<Path>> {
+        let _span = debug_span!("list_paths", %start, %end, max_ssn).entered();
         let mut result_paths: HashSet<Vec<Node>> = HashSet::new();
         // initial call stack has the start and its neighbors
-        let mut call_stack = vec![self.neighbors[&start].to_vec()];
+        let start_nbs = self
+            .neighbors
+            .get(&start)
+            .ok_or_else(|| AocError::unsolvable(format!("start node {} is not in the graph", start)))?;
+        let mut call_stack = vec![start_nbs.to_vec()];
         let mut current_path: Path = vec![start.clone()];
         let mut current_path_set: HashSet<Node> = current_path.iter().cloned().collect();
 
@@ -99,18 +141,15 @@ impl Graph {
             steps += 1;
 
             let current_stack: &mut Path = call_stack.last_mut().unwrap();
-            if DEBUG {
-                println!(
-                    "current_path {}, stack {}",
-                    fmt_path(&current_path),
-                    fmt_path(current_stack),
-                );
-            }
+            trace!(
+                step = steps,
+                path = %fmt_path(&current_path),
+                stack = %fmt_path(current_stack),
+                "dfs step"
+            );
 
             if current_stack.is_empty() {
-                if DEBUG {
-                    println!("current level exhusted, popping current path, popping call stack");
-                }
+                trace!("current level exhausted, popping current path and call stack");
                 let current_path_end = current_path.pop().unwrap();
                 current_path_set.remove(&current_path_end);
                 call_stack.pop();
@@ -137,19 +176,15 @@ impl Graph {
                 .filter(|n| is_admissible(n, &current_path, &start, max_ssn))
                 .cloned()
                 .collect();
-            if DEBUG {
-                println!("visiting {}, pushing neighbors on the stack {}", current_node, fmt_path(&nbs));
-            }
+            trace!(node = %current_node, neighbors = %fmt_path(&nbs), "visiting");
             call_stack.push(nbs);
         }
-        if DEBUG {
-            if steps >= MAX_STEPS {
-                println!("*** Ran out of fuel at step {} ***", steps);
-            } else {
-                println!("*** Total steps {} ***", steps);
-            }
+        if !call_stack.is_empty() {
+            warn!(steps, "ran out of fuel");
+            return Err(AocError::unsolvable(format!("ran out of fuel at step {}", steps)));
         }
-        result_paths.into_iter().collect()
+        debug!(steps, paths = result_paths.len(), "search finished");
+        Ok(result_paths.into_iter().collect())
     }
 }
 
@@ -167,7 +202,7 @@ impl Graph {
 /// - start: ref to the unique start node (which is never admissible to visit)
 /// - current_path: the current path explored so far
 /// - max_ssn: maximum number of times a (s)ingle (s)mall (n)ode may be visited on any
-///     admissible path
+///   admissible path
 fn is_admissible(test_node: &Node, current_path: PathSlice, start: &Node, max_ssn: usize) -> bool {
     if *test_node == *start {
         return false;
@@ -183,19 +218,37 @@ fn is_admissible(test_node: &Node, current_path: PathSlice, start: &Node, max_ss
         .filter(|(n, _c)| n.is_small())
         .map(|(_n, c)| *c)
         .max()
-        .unwrap();
+        .unwrap_or(0);
     let test_node_count = *counts.get(test_node).unwrap_or(&0);
     let path_has_the_max = max_small_count >= max_ssn;
     let path_has_the_node = test_node_count > 0;
-    if DEBUG {
-        println!(
-            "     isa: path {}, test_node {}, path_has_the_max {}, path_has_the_node {}, max_small_count {}, max_ssn {}",
-            fmt_path(current_path), test_node, path_has_the_max, path_has_the_node, max_small_count, max_ssn,
-        );
-    }
+    trace!(
+        path = %fmt_path(current_path),
+        %test_node,
+        path_has_the_max,
+        path_has_the_node,
+        max_small_count,
+        max_ssn,
+        "is admissible"
+    );
     !path_has_the_max || !path_has_the_node
 }
 
+/// Paths from start to end where a single small cave may be visited up to `max_ssn` times
+pub fn paths(graph: &Graph, max_ssn: usize) -> Result<Vec<Path>> {
+    graph.list_paths(Node::new("start"), Node::new("end"), max_ssn)
+}
+
+/// Number of paths from start to end visiting small caves at most once
+pub fn d12_part1(graph: &Graph) -> Result<usize> {
+    Ok(paths(graph, 1)?.len())
+}
+
+/// Number of paths from start to end where a single small cave may be visited twice
+pub fn d12_part2(graph: &Graph) -> Result<usize> {
+    Ok(paths(graph, 2)?.len())
+}
+
 // render graphs on the terminal, for fun and laughs
 impl fmt::Display for Graph {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -208,102 +261,82 @@ impl fmt::Display for Graph {
     }
 }
 
-pub fn parse_input(input_file: &str) -> Graph {
-    let content = util::read_to_string(input_file).unwrap();
+pub fn parse_input(input_file: &str) -> Result<Graph> {
+    let content = util::read_to_string(input_file)?;
     parse_input_from_string(&content)
 }
 
-pub fn parse_input_from_string(content: &str) -> Graph {
-    let edges: Vec<(Node, Node)> = content
-        .trim()
-        .split('\n')
-        .map(|line| {
-            let mut ps = line.trim().split('-');
-            let s = Node(ps.next().expect("could not parse edge start node").to_string());
-            let e = Node(ps.next().expect("could not parse edge end node").to_string());
-            (s, e)
-        })
-        .collect();
+pub fn parse_input_from_string(content: &str) -> Result<Graph> {
+    let bad_edge = |n| {
+        AocError::parse(n, "could not parse edge, expected `start-end`").expected("`start-end`")
+    };
+    let edges: Vec<(Node, Node)> = util::parse_lines(content, |n, line| {
+        let (s, e) = line.split_once('-').ok_or_else(|| bad_edge(n))?;
+        if s.is_empty() || e.is_empty() || e.contains('-') {
+            return Err(bad_edge(n));
+        }
+        Ok((Node::new(s), Node::new(e)))
+    })?;
     let mut neighbors: HashMap<Node, Vec<Node>> = HashMap::new();
     for (s, e) in edges {
         let s2 = s.clone();
         let e2 = e.clone();
         // add edge s -> e
-        let snbds = neighbors.entry(s).or_insert_with(Vec::new);
+        let snbds = neighbors.entry(s).or_default();
         snbds.push(e);
         // add edge e -> s
-        let enbds = neighbors.entry(e2).or_insert_with(Vec::new);
+        let enbds = neighbors.entry(e2).or_default();
         enbds.push(s2);
     }
-    Graph{neighbors}
+    Ok(Graph{neighbors})
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
 
     #[test]
     fn test_graph_parser() {
-        let test_graph = parse_input("inputs/d12_test");
+        let test_graph = parse_input("inputs/2021/d12_test").unwrap();
         println!("d12_test graph:\n{}", test_graph);
 
-        let graph = parse_input("inputs/d12");
+        let graph = parse_input("inputs/2021/d12").unwrap();
         println!("d12 graph:\n{}", graph);
-    }
 
-    #[test]
-    fn test_list_paths_test_graph1() {
-        let test_graph = parse_input("inputs/d12_test");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 1);
-        assert_eq!(paths.len(), 10);
+        assert!(matches!(
+            parse_input_from_string("start-A\nAb\nA-end"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
     }
 
     #[test]
-    fn test_list_paths_test_graph2() {
-        let test_graph = parse_input("inputs/d12_test2");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 1);
-        assert_eq!(paths.len(), 19);
+    fn test_list_paths_no_start() {
+        let graph = parse_input_from_string("a-b\nb-end").unwrap();
+        assert!(matches!(d12_part1(&graph), Err(AocError::Unsolvable(_))));
     }
 
     #[test]
-    fn test_list_paths_test_graph3() {
-        let test_graph = parse_input("inputs/d12_test3");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 1);
-        assert_eq!(paths.len(), 226);
+    #[cfg(feature = "tools")]
+    fn test_d12_answers() {
+        manifest::assert_answers(2021, 12);
     }
 
-    #[test]
-    fn test_d12_part1() {
-        let test_graph = parse_input("inputs/d12");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 1);
-        assert_eq!(paths.len(), 5178);
-    }
-
-    #[test]
-    fn test_d12_part2_test_graph1() {
-        let test_graph = parse_input("inputs/d12_test");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 2);
-        assert_eq!(paths.len(), 36);
-    }
-
-    #[test]
-    fn test_d12_part2_test_graph2() {
-        let test_graph = parse_input("inputs/d12_test2");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 2);
-        assert_eq!(paths.len(), 103);
-    }
-
-    #[test]
-    fn test_d12_part2_test_graph3() {
-        let test_graph = parse_input("inputs/d12_test3");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 2);
-        assert_eq!(paths.len(), 3509);
-    }
-
-    #[test]
-    fn test_d12_part2() {
-        let test_graph = parse_input("inputs/d12");
-        let paths = test_graph.list_paths(Node::new("start"), Node::new("end"), 2);
-        assert_eq!(paths.len(), 32879);
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            edges in prop::collection::vec(("[a-zA-Z]{1,5}", "[a-zA-Z]{1,5}"), 0..20)
+        ) {
+            let text: String = edges.iter().map(|(s, e)| format!("{}-{}\n", s, e)).collect();
+            let graph = parse_input_from_string(&text).unwrap();
+            for (s, e) in edges.iter() {
+                prop_assert!(graph.neighbors[&Node::new(s)].contains(&Node::new(e)));
+                prop_assert!(graph.neighbors[&Node::new(e)].contains(&Node::new(s)));
+            }
+            let degrees: usize = graph.neighbors.values().map(|nbs| nbs.len()).sum();
+            prop_assert_eq!(degrees, 2 * edges.len());
+        }
     }
 }
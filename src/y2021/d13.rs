@@ -0,0 +1,289 @@
+/// AoC 2021 -- Day 13
+/// https://adventofcode.com/2021/day/13
+use std::fmt;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::{grid::Grid, util};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 13;
+    type Input = Manual;
+    type Answer1 = usize;
+    type Answer2 = Letters;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d13_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Letters> {
+        d13_part2(input).map(Letters)
+    }
+}
+
+pub type DotGrid = Grid<bool>;
+
+/// The folded paper, read as the letters its dots draw
+pub struct Letters(pub DotGrid);
+
+impl From<Letters> for Answer {
+    fn from(letters: Letters) -> Answer {
+        Answer::Multiline(fmt_dots(&letters.0))
+    }
+}
+
+/// Fold instruction: fold up along a row (`y=`) or left along a column (`x=`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fold {
+    X(usize),
+    Y(usize),
+}
+
+impl fmt::Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fold::X(x) => write!(f, "fold along x={}", x),
+            Fold::Y(y) => write!(f, "fold along y={}", y),
+        }
+    }
+}
+
+/// The transparent paper's dots and the folding instructions
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manual {
+    pub dots: DotGrid,
+    pub folds: Vec<Fold>,
+}
+
+/// Prints the manual in the puzzle input format
+impl fmt::Display for Manual {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row, col, _) in self.dots.iter_pos_val().filter(|(_, _, &d)| d) {
+            writeln!(f, "{},{}", col, row)?;
+        }
+        writeln!(f)?;
+        for fold in self.folds.iter() {
+            writeln!(f, "{}", fold)?;
+        }
+        Ok(())
+    }
+}
+
+/// Largest paper accepted, in either dimension
+const MAX_PAPER_SIZE: usize = 10_000;
+
+pub fn parse_input(input_file: &str) -> Result<Manual> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+/// Parse `x,y` dot coordinates and `fold along x=N` instructions, in any order
+pub fn parse_input_from_string(content: &str) -> Result<Manual> {
+    let mut xys: Vec<(usize, usize)> = Vec::new();
+    let mut folds: Vec<Fold> = Vec::new();
+    util::parse_lines(content, |n, line| {
+        if let Some(f) = line.strip_prefix("fold along ") {
+            let (axis, pos) = f
+                .split_once('=')
+                .ok_or_else(|| AocError::parse(n, "expected `fold along x=N` or `y=N`"))?;
+            let field = pos;
+            let pos = util::parse_field::<usize>(line, field, n)?;
+            if pos >= MAX_PAPER_SIZE {
+                return Err(off_paper(n, format!("{} is", line), line, field));
+            }
+            match axis {
+                "x" => folds.push(Fold::X(pos)),
+                "y" => folds.push(Fold::Y(pos)),
+                _ => {
+                    let e = AocError::parse(n, format!("bad fold axis: {}", axis));
+                    return Err(e.at(line, axis).expected("`x` or `y`"));
+                }
+            }
+        } else {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| AocError::parse(n, "expected a dot `x,y`"))?;
+            let (x_field, y_field) = (x, y);
+            let x = util::parse_field::<usize>(line, x_field, n)?;
+            let y = util::parse_field::<usize>(line, y_field, n)?;
+            if x >= MAX_PAPER_SIZE || y >= MAX_PAPER_SIZE {
+                let field = if x >= MAX_PAPER_SIZE { x_field } else { y_field };
+                return Err(off_paper(n, format!("dot at ({}, {}) is", x, y), line, field));
+            }
+            xys.push((x, y));
+        }
+        Ok(())
+    })?;
+    if xys.is_empty() {
+        return Err(AocError::parse(0, "no dots"));
+    }
+    let max_y = xys.iter().map(|(_x, y)| *y).max().unwrap();
+    let max_x = xys.iter().map(|(x, _y)| *x).max().unwrap();
+    let (nrows, ncols) = (max_y + 1, max_x + 1);
+    let mut grid: DotGrid = Grid::new(nrows, ncols, false);
+    // (x, y) -> (col, row)
+    for (col, row) in xys {
+        grid.set(row, col, true);
+    }
+    Ok(Manual { dots: grid, folds })
+}
+
+/// Error for `what` on line `n` being off the paper, pointing at `field` of the line
+fn off_paper(n: usize, what: String, line: &str, field: &str) -> AocError {
+    let size = MAX_PAPER_SIZE;
+    AocError::parse(n, format!("{} off a {}x{} paper", what, size, size))
+        .at(line, field)
+        .expected(format!("less than {}", size))
+}
+
+/// Fold the paper, returning the new (smaller) grid of dots.
+///
+/// Dots beyond the fold line are reflected across it, the fold line itself is dropped. Returns
+/// an error if a dot would be reflected off the edge of the paper.
+pub fn fold(grid: &DotGrid, fold: Fold) -> Result<DotGrid> {
+    let (nrows, ncols) = match fold {
+        Fold::X(x) => (grid.rows, x),
+        Fold::Y(y) => (y, grid.cols),
+    };
+    let mut folded: DotGrid = Grid::new(nrows, ncols, false);
+    for (row, col, &dot) in grid.iter_pos_val() {
+        if !dot {
+            continue;
+        }
+        let (r, c) = match fold {
+            Fold::X(x) if col > x => (Some(row), (2 * x).checked_sub(col)),
+            Fold::Y(y) if row > y => ((2 * y).checked_sub(row), Some(col)),
+            _ => (Some(row), Some(col)),
+        };
+        match (r, c) {
+            (Some(r), Some(c)) if r < nrows && c < ncols => folded.set(r, c, true),
+            (Some(_), Some(_)) => continue,  // dot on the fold line
+            _ => {
+                return Err(AocError::invalid_state(format!(
+                    "{:?} reflects dot at ({}, {}) off the paper",
+                    fold, col, row
+                )))
+            }
+        }
+    }
+    Ok(folded)
+}
+
+/// Render dots as '#' and empty space as '.'
+pub fn fmt_dots(grid: &DotGrid) -> String {
+    grid.fmt_with_conversion(|b| if *b { "#".to_string() } else { ".".to_string() })
+}
+
+/// Number of dots visible after the first fold
+pub fn d13_part1(manual: &Manual) -> Result<usize> {
+    let first = *manual
+        .folds
+        .first()
+        .ok_or_else(|| AocError::unsolvable("no fold instructions"))?;
+    Ok(fold(&manual.dots, first)?.iter().filter(|&&d| d).count())
+}
+
+/// The paper before folding and after each fold, in order
+pub fn fold_steps(manual: &Manual) -> Result<Vec<DotGrid>> {
+    let mut steps = vec![manual.dots.clone()];
+    for &f in manual.folds.iter() {
+        let next = fold(steps.last().unwrap(), f)?;
+        steps.push(next);
+    }
+    Ok(steps)
+}
+
+/// The grid after all folds, which spells out the activation code
+pub fn d13_part2(manual: &Manual) -> Result<DotGrid> {
+    manual
+        .folds
+        .iter()
+        .try_fold(manual.dots.clone(), |grid, &f| fold(&grid, f))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    fn test_parse_test_input() {
+        let manual = parse_input("inputs/2021/d13_test").unwrap();
+        assert_eq!(manual.dots.get(0, 0), Some(&false));
+        assert_eq!(manual.folds, vec![Fold::Y(7), Fold::X(5)]);
+        println!("{}", fmt_dots(&manual.dots));
+
+        let manual = parse_input("inputs/2021/d13").unwrap();
+        assert_eq!(manual.folds.len(), 12);
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d13_answers() {
+        manifest::assert_answers(2021, 13);
+    }
+
+    #[test]
+    fn test_fold_steps() {
+        let manual = parse_input("inputs/2021/d13_test").unwrap();
+        let steps = fold_steps(&manual).unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0], manual.dots);
+        assert_eq!((steps[1].rows, steps[1].cols), (7, 11));
+        assert_eq!(steps[2], d13_part2(&manual).unwrap());
+    }
+
+    #[test]
+    fn test_d13_errors() {
+        assert!(matches!(
+            parse_input_from_string("1,2\n\nfold along z=3"),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+        let no_folds = parse_input_from_string("1,2\n3,4").unwrap();
+        assert!(matches!(d13_part1(&no_folds), Err(AocError::Unsolvable(_))));
+        let off_paper = parse_input_from_string("0,0\n9,0\n\nfold along x=2").unwrap();
+        assert!(matches!(d13_part1(&off_paper), Err(AocError::InvalidState(_))));
+
+        let content = "0,0\n\nfold along x=100000000000000";
+        let e = parse_input_from_string(content).unwrap_err();
+        assert!(matches!(e, AocError::Parse { line: 3, .. }));
+        assert_eq!(crate::diagnostic::locate(&e, content), Some((3, 14)));
+        let e = parse_input_from_string("0,0\n3,20000\n1,1\n").unwrap_err();
+        assert!(e.to_string().contains("dot at (3, 20000) is off"), "{}", e);
+        assert!(matches!(e, AocError::Parse { line: 2, .. }));
+    }
+
+    fn fold() -> impl Strategy<Value = Fold> {
+        prop_oneof![(1usize..60).prop_map(Fold::X), (1usize..60).prop_map(Fold::Y)]
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            dots in prop::collection::hash_set((0usize..50, 0usize..50), 1..40),
+            folds in prop::collection::vec(fold(), 0..5),
+        ) {
+            let mut text: String = dots.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect();
+            text.push('\n');
+            text += &folds.iter().map(|f| format!("{}\n", f)).collect::<String>();
+
+            let manual = parse_input_from_string(&text).unwrap();
+            let parsed_dots: HashSet<(usize, usize)> =
+                manual.dots.iter_pos_val().filter(|(_, _, &d)| d).map(|(r, c, _)| (c, r)).collect();
+            prop_assert_eq!(parsed_dots, dots);
+            prop_assert_eq!(&manual.folds, &folds);
+            prop_assert_eq!(parse_input_from_string(&manual.to_string()).unwrap(), manual);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+/// AoC 2021 -- Day 2
+/// https://adventofcode.com/2021/day/2
+use std::fmt;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::{Part, Solution};
+use crate::util;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 2;
+    type Input = Vec<Command>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i64> {
+        d2_part1(input)?.solution()
+    }
+
+    fn part2(input: &Self::Input) -> Result<i64> {
+        d2_part2(input)?.solution()
+    }
+
+    fn stream<R: BufRead>(part: Part, reader: R) -> Option<Result<Answer>> {
+        let interpreter = match part {
+            Part::One => d2_part1_interpreter,
+            Part::Two => d2_part2_interpreter,
+        };
+        Some(d2_streaming(reader, interpreter).and_then(|st| st.solution()).map(Answer::from))
+    }
+}
+
+pub struct State {
+    aim: i64,
+    horiz: i64,
+    depth: i64,
+}
+
+impl State {
+    /// Puzzle solution: final horizontal position times final depth
+    pub fn solution(&self) -> Result<i64> {
+        checked(self.horiz.checked_mul(self.depth))
+    }
+}
+
+/// The value of a checked operation, or an error if it overflowed
+fn checked(value: Option<i64>) -> Result<i64> {
+    value.ok_or_else(|| AocError::unsolvable("the submarine's position overflows an i64"))
+}
+
+/// A submarine command, e.g. `forward 5`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+    Forward(i64),
+    Up(i64),
+    Down(i64),
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Forward(x) => write!(f, "forward {}", x),
+            Command::Up(x) => write!(f, "up {}", x),
+            Command::Down(x) => write!(f, "down {}", x),
+        }
+    }
+}
+
+/// Parse the input file into a Vec of commands
+pub fn parse_input(input_file: &str) -> Result<Vec<Command>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<Command>> {
+    // turn lines -> commands
+    util::parse_lines(content, parse_command)
+}
+
+/// Parse line number `n`, a single command
+fn parse_command(n: usize, s: &str) -> Result<Command> {
+    let splits: Vec<&str> = s.split_whitespace().collect();
+    if splits.len() != 2 {
+        return Err(AocError::parse(n, "expected a command and an amount")
+            .expected("e.g. `forward 5`"));
+    }
+    let x = util::parse_field::<i64>(s, splits[1], n)?;
+    match splits[0] {
+        "forward" => Ok(Command::Forward(x)),
+        "up" => Ok(Command::Up(x)),
+        "down" => Ok(Command::Down(x)),
+        c => Err(AocError::parse(n, format!("invalid command: {}", c))
+            .at(s, c)
+            .expected("`forward`, `up` or `down`")),
+    }
+}
+
+pub fn d2_driver<F>(commands: &[Command], interpreter: F) -> Result<State>
+where
+    F: Fn(State, &Command) -> Result<State>,
+{
+    if commands.is_empty() {
+        return Err(AocError::unsolvable("no commands"));
+    }
+    commands.iter().try_fold(
+        State {
+            aim: 0,
+            horiz: 0,
+            depth: 0,
+        },
+        interpreter,
+    )
+}
+
+/// [`d2_driver`] straight from a reader, interpreting each command as it's read
+pub fn d2_streaming<R, F>(reader: R, interpreter: F) -> Result<State>
+where
+    R: BufRead,
+    F: Fn(State, &Command) -> Result<State>,
+{
+    let mut state = None;
+    util::for_each_line(reader, |n, line| {
+        let st = state.take().unwrap_or(State {
+            aim: 0,
+            horiz: 0,
+            depth: 0,
+        });
+        state = Some(interpreter(st, &parse_command(n, line)?)?);
+        Ok(())
+    })?;
+    state.ok_or_else(|| AocError::unsolvable("no commands"))
+}
+
+pub fn d2_part1(commands: &[Command]) -> Result<State> {
+    d2_driver(commands, d2_part1_interpreter)
+}
+
+pub fn d2_part2(commands: &[Command]) -> Result<State> {
+    d2_driver(commands, d2_part2_interpreter)
+}
+
+pub fn d2_part1_interpreter(st: State, cmd: &Command) -> Result<State> {
+    Ok(match *cmd {
+        Command::Forward(x) => State {
+            horiz: checked(st.horiz.checked_add(x))?,
+            ..st
+        },
+        Command::Up(x) => State {
+            depth: checked(st.depth.checked_sub(x))?,
+            ..st
+        },
+        Command::Down(x) => State {
+            depth: checked(st.depth.checked_add(x))?,
+            ..st
+        },
+    })
+}
+
+pub fn d2_part2_interpreter(st: State, cmd: &Command) -> Result<State> {
+    Ok(match *cmd {
+        Command::Forward(x) => State {
+            horiz: checked(st.horiz.checked_add(x))?,
+            depth: checked(st.aim.checked_mul(x).and_then(|d| st.depth.checked_add(d)))?,
+            ..st
+        },
+        Command::Up(x) => State {
+            aim: checked(st.aim.checked_sub(x))?,
+            ..st
+        },
+        Command::Down(x) => State {
+            aim: checked(st.aim.checked_add(x))?,
+            ..st
+        },
+    })
+}
+
+pub fn main() -> Result<()> {
+    let commands = parse_input("inputs/2021/d2")?;
+    let st_part1 = d2_part1(&commands)?;
+    println!(
+        "horiz {}, depth {}, solution {}",
+        st_part1.horiz,
+        st_part1.depth,
+        st_part1.solution()?
+    );
+
+    let st_part2 = d2_part2(&commands)?;
+    println!(
+        "horiz {}, depth {}, solution {}",
+        st_part2.horiz,
+        st_part2.depth,
+        st_part2.solution()?
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d2_answers() {
+        manifest::assert_answers(2021, 2);
+    }
+
+    #[test]
+    fn test_d2_parse_errors() {
+        let commands = parse_input_from_string("forward 5\ndown 5\n").unwrap();
+        assert_eq!(commands, vec![Command::Forward(5), Command::Down(5)]);
+        assert!(matches!(
+            parse_input_from_string("forward 5\nsideways 2\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            d2_streaming("forward 5\nsideways 2\n".as_bytes(), d2_part1_interpreter),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            d2_streaming("\n".as_bytes(), d2_part1_interpreter),
+            Err(AocError::Unsolvable(_))
+        ));
+    }
+
+    #[test]
+    fn test_d2_overflow() {
+        let commands = parse_input_from_string("forward 9223372036854775807\nforward 1\n").unwrap();
+        assert!(matches!(d2_part1(&commands), Err(AocError::Unsolvable(_))));
+        let commands = parse_input_from_string("forward 4294967296\ndown 4294967296\n").unwrap();
+        assert!(matches!(d2_part1(&commands).unwrap().solution(), Err(AocError::Unsolvable(_))));
+        let commands = parse_input_from_string("down 4294967296\nforward 4294967296\n").unwrap();
+        assert!(matches!(d2_part2(&commands), Err(AocError::Unsolvable(_))));
+    }
+
+    #[test]
+    fn test_d2_streaming_overflow() {
+        let text = "forward 1000000000000000000\n".repeat(20);
+        assert!(matches!(
+            d2_streaming(text.as_bytes(), d2_part1_interpreter),
+            Err(AocError::Unsolvable(_))
+        ));
+        let text = format!("down 1\n{}", "forward 1000000000000000000\n".repeat(5));
+        let streamed = Day2::stream(Part::Two, text.as_bytes()).unwrap();
+        assert!(matches!(streamed, Err(AocError::Unsolvable(_))));
+    }
+
+    fn command() -> impl Strategy<Value = Command> {
+        prop_oneof![
+            any::<i64>().prop_map(Command::Forward),
+            any::<i64>().prop_map(Command::Up),
+            any::<i64>().prop_map(Command::Down),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(commands in prop::collection::vec(command(), 0..50)) {
+            let text: String = commands.iter().map(|c| format!("{}\n", c)).collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), commands);
+        }
+
+        #[test]
+        fn test_streaming(commands in prop::collection::vec(command(), 1..50)) {
+            let commands: Vec<Command> = commands
+                .into_iter()
+                .map(|c| match c {
+                    Command::Forward(x) => Command::Forward(x % 100),
+                    Command::Up(x) => Command::Up(x % 100),
+                    Command::Down(x) => Command::Down(x % 100),
+                })
+                .collect();
+            let text: String = commands.iter().map(|c| format!("{}\n", c)).collect();
+            let streamed = d2_streaming(text.as_bytes(), d2_part2_interpreter).unwrap();
+            prop_assert_eq!(
+                streamed.solution().unwrap(),
+                d2_part2(&commands).unwrap().solution().unwrap()
+            );
+        }
+    }
+}
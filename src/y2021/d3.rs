@@ -0,0 +1,261 @@
+/// AoC 2021 -- Day 3
+/// https://adventofcode.com/2021/day/3
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::{Part, Solution, Variant};
+use crate::util;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 3;
+    type Input = Vec<Vec<bool>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d3_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        d3_part2(input)
+    }
+
+    /// Part 2 narrows down the whole list of bitvectors, so only part 1 streams
+    fn stream<R: BufRead>(part: Part, reader: R) -> Option<Result<Answer>> {
+        match part {
+            Part::One => Some(d3_part1_streaming(reader).map(Answer::from)),
+            Part::Two => None,
+        }
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![Variant {
+            name: "alt2",
+            part: Part::Two,
+            solve: |bvs| d3_part2_alt2(bvs).map(Answer::from),
+        }]
+    }
+}
+
+pub fn d3_part1(input_bvs: &[Vec<bool>]) -> Result<usize> {
+    let nbits = input_bvs
+        .first()
+        .ok_or_else(|| AocError::unsolvable("no bitvectors"))?
+        .len();
+    // gamma_rate has the most common bit at each index
+    let gamma_rate: Vec<bool> = (0..nbits)
+        .map(|i| mcb(&input_bvs.iter().collect::<Vec<&Vec<bool>>>(), i))
+        .collect();
+    // eps_rate is the bit-wise negation of gamma_rate
+    let eps_rate: Vec<bool> = gamma_rate.iter().map(|b| !*b).collect();
+    Ok(bv_to_int(&gamma_rate) * bv_to_int(&eps_rate))
+}
+
+/// [`d3_part1`] straight from a reader, counting the ones in each column as it goes
+pub fn d3_part1_streaming<R: BufRead>(reader: R) -> Result<usize> {
+    let mut ones: Vec<usize> = Vec::new();
+    let mut count = 0;
+    util::for_each_line(reader, |n, line| {
+        let bv = parse_bitvector(n, line)?;
+        if count == 0 {
+            ones = vec![0; bv.len()];
+        } else if bv.len() != ones.len() {
+            let msg = format!("expected {} bits, found {}", ones.len(), bv.len());
+            return Err(AocError::parse(n, msg));
+        }
+        for (o, b) in ones.iter_mut().zip(bv) {
+            *o += b as usize;
+        }
+        count += 1;
+        Ok(())
+    })?;
+    if count == 0 {
+        return Err(AocError::unsolvable("no bitvectors"));
+    }
+    // same tie break as `mcb`
+    let gamma_rate: Vec<bool> = ones.iter().map(|&o| 2 * o >= count).collect();
+    let eps_rate: Vec<bool> = gamma_rate.iter().map(|b| !*b).collect();
+    Ok(bv_to_int(&gamma_rate) * bv_to_int(&eps_rate))
+}
+
+pub fn d3_part2(input_bvs: &[Vec<bool>]) -> Result<usize> {
+    let oxy_rate = find_bv_by_selector(input_bvs, mcb)
+        .ok_or_else(|| AocError::unsolvable("no oxygen generator rating"))?;
+    let co2_rate = find_bv_by_selector(input_bvs, |bvs, i| !mcb(bvs, i))
+        .ok_or_else(|| AocError::unsolvable("no CO2 scrubber rating"))?;
+    Ok(bv_to_int(oxy_rate) * bv_to_int(co2_rate))
+}
+
+/// Alternate implementation of d3_part2, for comparison
+pub fn d3_part2_alt2(input_bvs: &[Vec<bool>]) -> Result<usize> {
+    let oxy_rate = find_bv_by_selector_alt2(input_bvs, true)
+        .ok_or_else(|| AocError::unsolvable("no oxygen generator rating"))?;
+    let co2_rate = find_bv_by_selector_alt2(input_bvs, false)
+        .ok_or_else(|| AocError::unsolvable("no CO2 scrubber rating"))?;
+    Ok(bv_to_int(oxy_rate) * bv_to_int(co2_rate))
+}
+
+/// parse an input file consisting of one bitstring per line into a vector of bitvectors
+pub fn input_to_bitvectors(input_file: &str) -> Result<Vec<Vec<bool>>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<Vec<bool>>> {
+    let mut width: Option<usize> = None;
+    util::parse_lines(content, |n, s| {
+        let bv = parse_bitvector(n, s)?;
+        match width {
+            Some(w) if w != bv.len() => Err(AocError::parse(
+                n,
+                format!("expected {} bits, found {}", w, bv.len()),
+            )),
+            _ => {
+                width = Some(bv.len());
+                Ok(bv)
+            }
+        }
+    })
+}
+
+/// Parse line number `n`, a string of bits
+fn parse_bitvector(n: usize, s: &str) -> Result<Vec<bool>> {
+    s.char_indices()
+        .map(|(i, c)| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(AocError::parse(n, format!("invalid bit: {:?}", c))
+                .at(s, &s[i..i + c.len_utf8()])
+                .expected("`0` or `1`")),
+        })
+        .collect()
+}
+
+/// Return the first bitvector after repreatedly filtering using the given column
+/// selector; avoids copying inner bitvectors. Returns None if filtering leaves no bitvectors.
+#[allow(clippy::manual_retain)]
+pub fn find_bv_by_selector<F>(bvs: &[Vec<bool>], selector: F) -> Option<&Vec<bool>>
+where
+    F: Fn(&[&Vec<bool>], usize) -> bool,
+{
+    let mut current_bvs: Vec<&Vec<bool>> = bvs.iter().collect();
+    let nbits = bvs.first()?.len();
+    // row selector, initially all rows are selected
+    // refs to bitvectors are copied on each iteration
+    for i in 0..nbits {
+        let b = selector(&current_bvs, i);
+        current_bvs = current_bvs.into_iter().filter(|&v| v[i] == b).collect();
+        if current_bvs.len() == 1 {
+            break;
+        }
+    }
+    current_bvs.first().copied()
+}
+
+/// Alternate implementation of find_bv_by_selector, for comparison
+/// This version avoids copying bitvector refs on every iteration, instead removing from a hash
+/// set. It also avoids consuming a closure. It is ~100x slower :sob:
+pub fn find_bv_by_selector_alt2(bvs: &[Vec<bool>], most_common: bool) -> Option<&Vec<bool>> {
+    let mut current_bvs: HashSet<&Vec<bool>> = bvs.iter().collect();
+    let nbits = bvs.first()?.len();
+    // in this version, bitvector refs are not copied on every iteration
+    for i in 0..nbits {
+        let b = mcb_alt2(&current_bvs, i) == most_common;
+        for v in bvs.iter() {
+            if current_bvs.contains(v) && v[i] != b {
+                current_bvs.remove(v);
+            }
+        }
+        if current_bvs.len() == 1 {
+            break;
+        }
+    }
+    current_bvs.into_iter().next()
+}
+
+/// Convert bitvector to unsigned int
+pub fn bv_to_int(bv: &[bool]) -> usize {
+    bv.iter()
+        .fold(0, |acc, &x| (acc << 1) + (if x { 1 } else { 0 }))
+}
+
+/// Most common bit at given column index
+pub fn mcb(bvs: &[&Vec<bool>], index: usize) -> bool {
+    2 * bvs.iter().filter(|&&v| v[index]).count() >= bvs.len()
+}
+
+/// Most common bit, hashset version
+pub fn mcb_alt2(bvs: &HashSet<&Vec<bool>>, index: usize) -> bool {
+    2 * bvs.iter().filter(|&&v| v[index]).count() >= bvs.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    fn test_mcb() {
+        let input_bvs = input_to_bitvectors("inputs/2021/d3_test").unwrap();
+        assert!(mcb(&input_bvs.iter().collect::<Vec<&Vec<bool>>>(), 0));
+        assert!(!mcb(&input_bvs.iter().collect::<Vec<&Vec<bool>>>(), 1));
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d3_answers() {
+        manifest::assert_answers(2021, 3);
+    }
+
+    #[test]
+    fn test_d3_part2_alt2() {
+        let input_bvs = input_to_bitvectors("inputs/2021/d3").unwrap();
+        assert_eq!(d3_part2_alt2(&input_bvs).unwrap(), d3_part2(&input_bvs).unwrap());
+    }
+
+    #[test]
+    fn test_d3_errors() {
+        assert!(matches!(d3_part1(&[]), Err(AocError::Unsolvable(_))));
+        assert!(matches!(d3_part2(&[]), Err(AocError::Unsolvable(_))));
+        assert!(matches!(d3_part1_streaming("".as_bytes()), Err(AocError::Unsolvable(_))));
+        assert!(matches!(
+            d3_part1_streaming("01\n011\n".as_bytes()),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_d3_part1_streaming() {
+        for input in ["inputs/2021/d3_test", "inputs/2021/d3"] {
+            let reader = std::io::BufReader::new(std::fs::File::open(input).unwrap());
+            let expected = d3_part1(&input_to_bitvectors(input).unwrap()).unwrap();
+            assert_eq!(d3_part1_streaming(reader).unwrap(), expected);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            bvs in (1usize..20).prop_flat_map(|w| {
+                prop::collection::vec(prop::collection::vec(any::<bool>(), w), 0..30)
+            })
+        ) {
+            let text: String = bvs
+                .iter()
+                .map(|bv| bv.iter().map(|&b| if b { '1' } else { '0' }).chain(Some('\n')).collect::<String>())
+                .collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), bvs);
+        }
+    }
+}
@@ -0,0 +1,286 @@
+/// AoC 2021 -- Day 4
+/// https://adventofcode.com/2021/day/4
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::util;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 4;
+    type Input = Bingo;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        d4_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        d4_part2(input)
+    }
+}
+
+pub fn main() -> Result<()> {
+    let bingo = parse_input("inputs/2021/d4")?;
+    println!("first winning board score: {}", d4_part1(&bingo)?);
+    println!("last winning board score: {}", d4_part2(&bingo)?);
+    Ok(())
+}
+
+/// Bingo calls, along with the boards to play on
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bingo {
+    calls: Vec<u32>,
+    boards: Vec<Board>,
+}
+
+/// Parse the bingo calls and boards from an input file.
+///
+/// The first line holds the comma separated calls, all remaining numbers are read in groups of
+/// 25 as boards. This accepts both the original puzzle input and the munged one-board-per-line
+/// variant.
+pub fn parse_input(input_file: &str) -> Result<Bingo> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Bingo> {
+    let mut lines = util::numbered_lines(content);
+    let (first_n, first_line) = lines
+        .next()
+        .ok_or_else(|| AocError::parse(0, "empty input"))?;
+    let calls: Vec<u32> = first_line
+        .split(',')
+        .map(|w| util::parse_field::<u32>(first_line, w, first_n))
+        .collect::<Result<_>>()?;
+    let mut values: Vec<u32> = Vec::new();
+    let mut last_n = first_n;
+    for (n, line) in lines {
+        for w in line.split_whitespace() {
+            values.push(util::parse_field::<u32>(line, w, n)?);
+        }
+        last_n = n;
+    }
+    if !values.len().is_multiple_of(25) {
+        return Err(AocError::parse(
+            last_n,
+            format!("expected 5x5 boards, found {} trailing numbers", values.len() % 25),
+        ));
+    }
+    let boards = values.chunks(25).map(Board::from_u32s).collect();
+    Ok(Bingo { calls, boards })
+}
+
+/// Play bingo!
+///
+/// Returns the winning scores in the order that boards win. The first score is for the first
+/// board to win, the last score for the last board to win, or an error if a score overflows.
+pub fn play_bingo(bingo: &Bingo) -> Result<Vec<u32>> {
+    let mut boards = bingo.boards.clone();
+    let mut scores = Vec::new();
+    let mut boards_won: HashSet<usize> = HashSet::new();
+    for &call in bingo.calls.iter() {
+        for (i, board) in boards.iter_mut().enumerate() {
+            if boards_won.contains(&i) {
+                continue;
+            }
+            if board.mark_and_win(call) {
+                let score = board.sum_unmarked().and_then(|sum| sum.checked_mul(call));
+                scores.push(score.ok_or_else(|| {
+                    AocError::unsolvable(format!("board {}'s score overflows a u32", i + 1))
+                })?);
+                boards_won.insert(i);
+            }
+        }
+    }
+    Ok(scores)
+}
+
+/// Score of the first board to win
+pub fn d4_part1(bingo: &Bingo) -> Result<u32> {
+    play_bingo(bingo)?
+        .first()
+        .copied()
+        .ok_or_else(|| AocError::unsolvable("no board wins"))
+}
+
+/// Score of the last board to win
+pub fn d4_part2(bingo: &Bingo) -> Result<u32> {
+    play_bingo(bingo)?
+        .last()
+        .copied()
+        .ok_or_else(|| AocError::unsolvable("no board wins"))
+}
+
+/// row, col position on a board
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Pos(usize, usize);
+
+/// cell represents a board value and mark state
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Cell {
+    value: u32,
+    mark: bool,
+}
+
+impl Cell {
+    pub fn new(value: u32, mark: bool) -> Self {
+        Cell { value, mark }
+    }
+}
+
+// a 5x5 board of cells
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    // position -> cell
+    cellmap: HashMap<Pos, Cell>,
+    // cell value -> position
+    posmap: HashMap<u32, Pos>,
+}
+
+impl Board {
+    /// Build a board from its 25 values, in row order. Panics if there aren't exactly 25.
+    pub fn from_u32s(us: &[u32]) -> Self {
+        assert!(us.len() == 25);
+        let mut cellmap: HashMap<Pos, Cell> = HashMap::new();
+        let mut posmap: HashMap<u32, Pos> = HashMap::new();
+        for (i, &u) in us.iter().enumerate() {
+            let pos = Pos(i / 5, i % 5);
+            let cell = Cell::new(u, false);
+            cellmap.insert(pos, cell);
+            posmap.insert(u, pos);
+        }
+        Board { cellmap, posmap }
+    }
+
+    // check if the (row, col) is marked; assume row, col are in bounds
+    fn is_marked(&self, row: usize, col: usize) -> bool {
+        self.cellmap.get(&Pos(row, col)).unwrap().mark
+    }
+
+    // Mark the number on the board, mutating the board. If the number was present on the board and
+    // marking results in a winning board, return `true`, otherwise `false`.
+    pub fn mark_and_win(&mut self, num: u32) -> bool {
+        if !self.posmap.contains_key(&num) {
+            return false;
+        }
+        let pos = self.posmap.get(&num).unwrap();
+        let newcell = Cell::new(num, true);
+        self.cellmap.insert(*pos, newcell);
+
+        // check for row win along row == pos.0
+        let rowwin = (0..5).all(|col| self.is_marked(pos.0, col));
+        // check for col win along col == pos.1
+        let colwin = (0..5).all(|row| self.is_marked(row, pos.1));
+        rowwin || colwin
+    }
+
+    /// Sum of the unmarked values, or None if it overflows a u32
+    pub fn sum_unmarked(&self) -> Option<u32> {
+        self.cellmap
+            .values()
+            .filter(|c| !c.mark)
+            .try_fold(0u32, |sum, c| sum.checked_add(c.value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    pub fn test_mark_board() {
+        let grid = vec![
+            22, 13, 17, 11, 0, 8, 2, 23, 4, 24, 21, 9, 14, 16, 7, 6, 10, 3, 18, 5, 1, 12, 20, 15,
+            19,
+        ];
+        let grid_sum: u32 = grid.iter().sum();
+        let mut board1 = Board::from_u32s(&grid);
+        // test a column win, called in row order
+        assert!(!board1.mark_and_win(22));
+        assert!(!board1.mark_and_win(8));
+        assert!(!board1.mark_and_win(21));
+        assert!(!board1.mark_and_win(6));
+        // WIN
+        assert!(board1.mark_and_win(1));
+        assert_eq!(board1.sum_unmarked(), Some(grid_sum - 22 - 8 - 21 - 6 - 1));
+
+        // test a row win, in a non-sequential order with extra calls
+        let mut board2 = Board::from_u32s(&grid);
+        assert!(!board2.mark_and_win(6));
+        assert!(!board2.mark_and_win(3));
+        assert!(!board2.mark_and_win(5));
+        assert!(!board2.mark_and_win(18));
+        assert!(!board2.mark_and_win(0)); // not in row
+        assert!(!board2.mark_and_win(19)); // not in row
+                                           // WIN
+        assert!(board2.mark_and_win(10));
+        assert_eq!(board2.sum_unmarked(), Some(grid_sum - 6 - 3 - 5 - 18 - 19 - 10));
+
+        // test a diagonal non-win
+        let mut board3 = Board::from_u32s(&grid);
+        assert!(!board3.mark_and_win(0));
+        assert!(!board3.mark_and_win(4));
+        assert!(!board3.mark_and_win(14));
+        assert!(!board3.mark_and_win(10));
+        assert!(!board3.mark_and_win(1));
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d4_answers() {
+        manifest::assert_answers(2021, 4);
+    }
+
+    #[test]
+    fn test_d4_errors() {
+        let bingo = Bingo {
+            calls: vec![99],
+            boards: vec![Board::from_u32s(&(0..25).collect::<Vec<u32>>())],
+        };
+        assert!(matches!(d4_part1(&bingo), Err(AocError::Unsolvable(_))));
+
+        // the first row wins on 4000000000, leaving 20 unmarked 1s to multiply it by
+        let mut values = vec![1; 25];
+        values[..5].copy_from_slice(&[0, 1000000000, 2000000000, 3000000000, 4000000000]);
+        let bingo = Bingo {
+            calls: vec![0, 1000000000, 2000000000, 3000000000, 4000000000],
+            boards: vec![Board::from_u32s(&values)],
+        };
+        let result = d4_part1(&bingo);
+        assert!(matches!(result, Err(AocError::Unsolvable(m)) if m.contains("overflows")));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            calls in prop::collection::vec(0u32..100, 1..30),
+            boards in prop::collection::vec(prop::collection::vec(0u32..100, 25), 0..4),
+        ) {
+            let mut text = calls.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(",");
+            text.push('\n');
+            for board in boards.iter() {
+                text.push('\n');
+                for row in board.chunks(5) {
+                    let row: Vec<String> = row.iter().map(|v| format!("{:>2}", v)).collect();
+                    text += &format!("{}\n", row.join(" "));
+                }
+            }
+            let bingo = parse_input_from_string(&text).unwrap();
+            prop_assert_eq!(bingo.calls, calls);
+            prop_assert_eq!(bingo.boards, boards.iter().map(|b| Board::from_u32s(b)).collect::<Vec<Board>>());
+        }
+    }
+}
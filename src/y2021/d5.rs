@@ -0,0 +1,324 @@
+/// AoC 2021 -- Day 5
+/// https://adventofcode.com/2021/day/5
+use std::collections::HashMap;
+use std::fmt;
+
+use regex::Regex;
+
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::util;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 5;
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d5_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        d5_part2(input)
+    }
+}
+
+/// 2d line on an integer grid
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Line {
+    start: (i64, i64),
+    end: (i64, i64),
+}
+
+impl Line {
+    // create a new line, normalizing start/end to be lexicographic
+    fn new(x0: i64, y0: i64, x1: i64, y1: i64) -> Self {
+        if x0 > x1 || x0 == x1 && y0 > y1 {
+            Line {
+                start: (x1, y1),
+                end: (x0, y0),
+            }
+        } else {
+            Line {
+                start: (x0, y0),
+                end: (x1, y1),
+            }
+        }
+    }
+
+    fn is_horiz(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    fn is_vert(&self) -> bool {
+        self.start.0 == self.end.0
+    }
+
+    fn is_diagonal(&self) -> bool {
+        (self.end.0 - self.start.0).abs() == (self.end.1 - self.start.1).abs()
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{} -> {},{}", self.start.0, self.start.1, self.end.0, self.end.1)
+    }
+}
+
+/// 2d integer grid position
+type Pos = (i64, i64);
+
+/// A 2d integer grid containing counts at each coordinate.
+///
+/// Implemented as a map of position -> #intersecting vents. The upper left corner is (0,0), x
+/// increases to the right, y increases downwards
+#[derive(Debug)]
+struct Grid(HashMap<Pos, usize>);
+
+impl Grid {
+    fn new() -> Self {
+        Grid(HashMap::new())
+    }
+
+    fn get_count(&self, pos: &Pos) -> usize {
+        *self.0.get(pos).unwrap_or(&0)
+    }
+
+    fn xmax(&self) -> Option<i64> {
+        self.0.keys().map(|&p| p.0).max()
+    }
+
+    fn ymax(&self) -> Option<i64> {
+        self.0.keys().map(|&p| p.1).max()
+    }
+
+    /// Dense copy of the counts, indexed by (y, x)
+    fn to_map(&self) -> crate::grid::Grid<usize> {
+        let rows = self.ymax().map_or(0, |y| y + 1) as usize;
+        let cols = self.xmax().map_or(0, |x| x + 1) as usize;
+        let mut map = crate::grid::Grid::new(rows, cols, 0);
+        for (&(x, y), &count) in &self.0 {
+            map.set(y as usize, x as usize, count);
+        }
+        map
+    }
+
+    fn increment(&mut self, pos: Pos) {
+        self.0.insert(pos, self.get_count(&pos) + 1);
+    }
+
+    fn add_line(&mut self, line: Line) {
+        if line.is_horiz() {
+            for x in line.start.0..=line.end.0 {
+                self.increment((x, line.start.1));
+            }
+        } else if line.is_vert() {
+            for y in line.start.1..=line.end.1 {
+                self.increment((line.start.0, y));
+            }
+        } else {
+            // line is diagonal, checked by the parser
+            let dy = line.end.1 - line.start.1;
+            let dx = line.end.0 - line.start.0;
+            let slope = dy / dx;
+            let mut y = line.start.1;
+            for x in line.start.0..=line.end.0 {
+                self.increment((x, y));
+                y += slope;
+            }
+        }
+    }
+}
+
+// render grids on the terminal, just for fun :)
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let xmax = self.xmax().unwrap_or(0);
+        let ymax = self.ymax().unwrap_or(0);
+        let mut result = String::new();
+        for y in 0..=ymax {
+            for x in 0..=xmax {
+                let count = self.get_count(&(x, y));
+                let marker = if count > 0 {
+                    format!("{}", count)
+                } else {
+                    ".".to_string()
+                };
+                result += &marker;
+            }
+            result += "\n";
+        }
+        writeln!(f, "{}", result)
+    }
+}
+
+/// Bound on the coordinates accepted, in either direction. Puzzle inputs stay below 1000, and
+/// this keeps the dense [`vent_map`] to a million counts.
+const MAX_FLOOR_SIZE: i64 = 1_000;
+
+pub fn parse_input(input_file: &str) -> Result<Vec<Line>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<Line>> {
+    let line_re = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+    util::parse_lines(content, |n, tr_line| {
+        let captures = line_re.captures(tr_line).ok_or_else(|| {
+            AocError::parse(n, "expected a line segment `x0,y0 -> x1,y1`")
+                .at(tr_line, unmatched_suffix(tr_line))
+                .expected("`x0,y0 -> x1,y1`")
+        })?;
+        let coords: Vec<i64> = (1..=4)
+            .map(|i| {
+                let coord = util::parse_field::<i64>(tr_line, &captures[i], n)?;
+                if coord >= MAX_FLOOR_SIZE {
+                    let size = MAX_FLOOR_SIZE;
+                    let msg = format!("coordinate {} is off a {}x{} floor", coord, size, size);
+                    return Err(AocError::parse(n, msg)
+                        .at(tr_line, &captures[i])
+                        .expected(format!("less than {}", MAX_FLOOR_SIZE)));
+                }
+                Ok(coord)
+            })
+            .collect::<Result<_>>()?;
+        let line = Line::new(coords[0], coords[1], coords[2], coords[3]);
+        if !(line.is_horiz() || line.is_vert() || line.is_diagonal()) {
+            return Err(AocError::parse(
+                n,
+                "line segment is not horizontal, vertical or at 45 degrees",
+            ));
+        }
+        Ok(line)
+    })
+}
+
+/// The part of `line` from where it stops looking like `x0,y0 -> x1,y1`
+fn unmatched_suffix(line: &str) -> &str {
+    let mut rest = line;
+    for token in ["0", ",", "0", " -> ", "0", ",", "0"] {
+        let len = match token {
+            "0" => rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len(),
+            _ if rest.starts_with(token) => token.len(),
+            _ => 0,
+        };
+        if len == 0 {
+            break;
+        }
+        rest = &rest[len..];
+    }
+    rest
+}
+
+pub fn d5_part1(vents: &[Line]) -> Result<usize> {
+    let mut grid = Grid::new();
+    for &line in vents {
+        if line.is_horiz() || line.is_vert() {
+            grid.add_line(line);
+        }
+    }
+    Ok(grid.0.values().filter(|&&c| c > 1).count())
+}
+
+pub fn d5_part2(vents: &[Line]) -> Result<usize> {
+    let mut grid = Grid::new();
+    for &line in vents {
+        grid.add_line(line);
+    }
+    Ok(grid.0.values().filter(|&&c| c > 1).count())
+}
+
+/// Number of vents over each point of the floor, optionally counting diagonal vents, e.g. for
+/// rendering
+pub fn vent_map(vents: &[Line], diagonals: bool) -> crate::grid::Grid<usize> {
+    let mut grid = Grid::new();
+    for &line in vents {
+        if diagonals || line.is_horiz() || line.is_vert() {
+            grid.add_line(line);
+        }
+    }
+    grid.to_map()
+}
+
+pub fn main() -> Result<()> {
+    let vents = parse_input("inputs/2021/d5")?;
+    println!(
+        "# dangerous areas (w/o diagonal vents): {}",
+        d5_part1(&vents)?
+    );
+    println!("# dangerous areas (all vents): {}", d5_part2(&vents)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d5_answers() {
+        manifest::assert_answers(2021, 5);
+    }
+
+    #[test]
+    fn test_d5_parse_errors() {
+        assert_eq!(parse_input_from_string("0,9 -> 5,9\n\n8,0 -> 0,8\n").unwrap().len(), 2);
+        assert!(matches!(
+            parse_input_from_string("0,9 -> 5,9\n1,2 -> 3\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_input_from_string("0,0 -> 1,2\n"),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+        let content = "0,9 -> 5,9\n0,0 -> 999999999,999999999\n";
+        let e = parse_input_from_string(content).unwrap_err();
+        assert!(matches!(e, AocError::Parse { line: 2, .. }));
+        assert_eq!(crate::diagnostic::locate(&e, content), Some((2, 8)));
+        assert_eq!(parse_input_from_string("0,0 -> 999,999\n").unwrap().len(), 1);
+        assert!(matches!(
+            parse_input_from_string("0,0 -> 1000,1000\n"),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_d5_diagnostics() {
+        let content = "1,2 -> 3\n0,9 -> 5,9\n1,2 => 3,4\n";
+        let e = parse_input_from_string(content).unwrap_err();
+        assert_eq!(crate::diagnostic::locate(&e, content), Some((1, 9)));
+        let rendered = crate::diagnostic::render(&e, "d5", content);
+        assert!(rendered.contains("--> d5:3:4\n"), "{}", rendered);
+        assert!(rendered.contains("  |    ^^^^^^^ expected `x0,y0 -> x1,y1`\n"), "{}", rendered);
+    }
+
+    /// Horizontal, vertical or diagonal line segments
+    fn line() -> impl Strategy<Value = Line> {
+        (0i64..1000, 0i64..1000, 0i64..1000, 0usize..4).prop_map(|(x, y, len, dir)| {
+            let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][dir];
+            // keep coordinates on the floor
+            let len = if dy < 0 { len.min(y) } else { len.min(999 - y) };
+            let len = len.min(999 - x);
+            Line::new(x, y, x + dx * len, y + dy * len)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(lines in prop::collection::vec(line(), 0..30)) {
+            let text: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), lines);
+        }
+    }
+}
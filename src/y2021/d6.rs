@@ -0,0 +1,364 @@
+/// AoC 2021 -- Day 6
+/// https://adventofcode.com/2021/day/6
+///
+/// Alternate solution that mutates fish map in-place during simulation.
+///
+/// Benmark Before (best of 5):
+///
+/// ```text
+/// $ target/release/d6
+/// [test] number of fish after 80 days: 5934
+/// number of fish after 80 days: 374927 (duration 91.125µs)
+/// [test] number of fish after 256 days: 26984457539
+/// number of fish after 256 days: 1687617803407 (duration 153.75µs)
+/// ```
+///
+/// Benchmark After (best of 5):
+///
+/// ```text
+/// $ target/release/d6
+/// [test] number of fish after 80 days: 5934
+/// number of fish after 80 days: 374927 (duration 87.083µs)
+/// [test] number of fish after 256 days: 26984457539
+/// number of fish after 256 days: 1687617803407 (duration 156.041µs)
+/// ```
+///
+/// There appears to be no performance difference.
+///
+/// Benchmark after refactoring FishMap to [u64; 9]:
+///
+/// ```text
+/// [test] number of fish after 80 days: 5934
+/// number of fish after 80 days: 374927 (duration 37.458µs)
+/// [test] number of fish after 256 days: 26984457539
+/// number of fish after 256 days: 1687617803407 (duration 20.875µs)
+/// ```
+///
+/// Roughly 8x faster...
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::time::Instant;
+
+use crate::answer::Answer;
+use crate::error::{AocError, Result};
+use crate::solution::{Part, Solution, Variant};
+use crate::util;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 6;
+    type Input = Vec<usize>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64> {
+        d6_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64> {
+        d6_part2(input)
+    }
+
+    fn stream<R: BufRead>(part: Part, reader: R) -> Option<Result<Answer>> {
+        let ticks = match part {
+            Part::One => 80,
+            Part::Two => 256,
+        };
+        Some(fish_map_from_reader(reader).and_then(|m| count_after(m, ticks)).map(Answer::from))
+    }
+
+    fn variants() -> Vec<Variant<Self::Input>> {
+        vec![
+            Variant {
+                name: "hashmap",
+                part: Part::One,
+                solve: |timers| exec_fish_simulator_hashmap(timers, 80).map(Answer::from),
+            },
+            Variant {
+                name: "hashmap",
+                part: Part::Two,
+                solve: |timers| exec_fish_simulator_hashmap(timers, 256).map(Answer::from),
+            },
+        ]
+    }
+}
+
+pub fn parse_input(input_file: &str) -> Result<Vec<usize>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<usize>> {
+    let lines = util::parse_lines(content, |n, line| {
+        line.split(',')
+            .map(|s| {
+                let t = util::parse_field::<usize>(line, s, n)?;
+                if t > MAX_TIMER {
+                    let e = AocError::parse(n, format!("fish timer {} is out of range", t));
+                    return Err(e.at(line, s).expected("a timer from 0 to 8"));
+                }
+                Ok(t)
+            })
+            .collect::<Result<Vec<usize>>>()
+    })?;
+    Ok(lines.concat())
+}
+
+/// Largest legal fish timer value
+const MAX_TIMER: usize = 8;
+
+/// Longest timer field [`fish_map_from_reader`] will hold on to before giving up on it
+const MAX_FIELD_LEN: usize = 32;
+
+/// Count the fish timers in puzzle input format read from `reader`. Reads a byte at a time and
+/// only keeps the current field, so even an enormous single line takes constant memory.
+pub fn fish_map_from_reader<R: BufRead>(reader: R) -> Result<FishMap> {
+    let mut fish_map = [0; 9];
+    let mut field: Vec<u8> = Vec::new();
+    let mut n = 1;
+    // whether the current line has a comma, so that an empty field on it is an error
+    let mut in_list = false;
+    let mut add = |field: &[u8], n: usize| -> Result<()> {
+        let t = util::parse_num::<usize>(&String::from_utf8_lossy(field), n)?;
+        if t > MAX_TIMER {
+            return Err(AocError::parse(n, format!("fish timer {} is out of range", t)));
+        }
+        inc_fish(&mut fish_map, t, 1)
+    };
+    for byte in reader.bytes() {
+        match byte? {
+            b',' => {
+                add(&field, n)?;
+                field.clear();
+                in_list = true;
+            }
+            b'\n' => {
+                if in_list || !field.trim_ascii().is_empty() {
+                    add(&field, n)?;
+                }
+                field.clear();
+                in_list = false;
+                n += 1;
+            }
+            _ if field.len() >= MAX_FIELD_LEN => {
+                return Err(AocError::parse(n, "expected a comma separated list of timers"));
+            }
+            b => field.push(b),
+        }
+    }
+    if in_list || !field.trim_ascii().is_empty() {
+        add(&field, n)?;
+    }
+    Ok(fish_map)
+}
+
+// Track fish timers using a fixed sized array of ints:
+//   * array index = fish timer value
+//   * array value = number of fish with a specific timer value
+pub type FishMap = [u64; 9];
+
+/// Add `amt` fish to the map with timer value `timer`, or fail if there are too many to count
+fn inc_fish(fish_map: &mut FishMap, timer: usize, amt: u64) -> Result<()> {
+    fish_map[timer] = checked(fish_map[timer].checked_add(amt))?;
+    Ok(())
+}
+
+/// The value of a checked operation, or an error if it overflowed
+fn checked(value: Option<u64>) -> Result<u64> {
+    value.ok_or_else(|| AocError::unsolvable("the number of fish overflows a u64"))
+}
+
+fn dec_fish(fish_map: &mut FishMap, timer: usize, amt: u64) {
+    fish_map[timer] -= amt;
+}
+
+/// 1 fish, 2 fish, red fish, blue fish
+fn count_fish(fish_map: FishMap) -> Result<u64> {
+    checked(fish_map.iter().try_fold(0u64, |sum, &amt| sum.checked_add(amt)))
+}
+
+/// Simulate one tick of the clock, mutating the given `fish_map` in-place
+fn simulate(fish_map: &mut FishMap) -> Result<()> {
+    let mut prev_zero_fish: u64 = 0;
+    // the only legal timer values are 0..8
+    for t in 0..=8 {
+        let v = fish_map[t];
+        if t == 0 {
+            prev_zero_fish = v;
+            dec_fish(fish_map, 0, v);
+        } else {
+            inc_fish(fish_map, t - 1, v)?;
+            dec_fish(fish_map, t, v);
+        }
+    }
+    // add the new spawned fish last so that we don't simulate them in this tick
+    // spawn v new fish with timer 8
+    inc_fish(fish_map, 8, prev_zero_fish)?;
+    // reset 0 timer fish to 6
+    inc_fish(fish_map, 6, prev_zero_fish)
+}
+
+fn init_fish_map(init_fish_timers: &[usize]) -> Result<FishMap> {
+    let mut fish_map = [0; 9];
+    for &t in init_fish_timers {
+        if t > MAX_TIMER {
+            return Err(AocError::invalid_state(format!("fish timer {} is out of range", t)));
+        }
+        inc_fish(&mut fish_map, t, 1)?;
+    }
+    Ok(fish_map)
+}
+
+fn exec_fish_simulator(init_fish_timers: &[usize], ticks: u64) -> Result<u64> {
+    count_after(init_fish_map(init_fish_timers)?, ticks)
+}
+
+/// The simulation as it was before FishMap became an array, keyed by timer value in a HashMap.
+/// Kept to check the array version against, see the benchmarks at the top.
+fn exec_fish_simulator_hashmap(init_fish_timers: &[usize], ticks: u64) -> Result<u64> {
+    let mut fish_map: HashMap<usize, u64> = HashMap::new();
+    for &t in init_fish_timers {
+        if t > MAX_TIMER {
+            return Err(AocError::invalid_state(format!("fish timer {} is out of range", t)));
+        }
+        *fish_map.entry(t).or_default() += 1;
+    }
+    for _ in 1..=ticks {
+        let mut next: HashMap<usize, u64> = HashMap::new();
+        for (&t, &amt) in fish_map.iter() {
+            let timers: &[usize] = if t == 0 { &[6, 8] } else { &[t - 1] };
+            for &t in timers {
+                let count = next.entry(t).or_default();
+                *count = checked(count.checked_add(amt))?;
+            }
+        }
+        fish_map = next;
+    }
+    checked(fish_map.values().try_fold(0u64, |sum, &amt| sum.checked_add(amt)))
+}
+
+/// Number of fish after simulating `ticks` days
+fn count_after(mut fish_map: FishMap, ticks: u64) -> Result<u64> {
+    for _ in 1..=ticks {
+        simulate(&mut fish_map)?;
+    }
+    count_fish(fish_map)
+}
+
+/// Fish per timer value on each day from the start through `ticks`
+pub fn populations(init_fish_timers: &[usize], ticks: u64) -> Result<Vec<FishMap>> {
+    let mut fish_map = init_fish_map(init_fish_timers)?;
+    let mut maps = vec![fish_map];
+    for _ in 1..=ticks {
+        simulate(&mut fish_map)?;
+        maps.push(fish_map);
+    }
+    Ok(maps)
+}
+
+pub fn d6_part1(init_fish_timers: &[usize]) -> Result<u64> {
+    exec_fish_simulator(init_fish_timers, 80)
+}
+
+pub fn d6_part2(init_fish_timers: &[usize]) -> Result<u64> {
+    exec_fish_simulator(init_fish_timers, 256)
+}
+
+pub fn main() -> Result<()> {
+    println!(
+        "[test] number of fish after 80 days: {}",
+        d6_part1(&parse_input("inputs/2021/d6_test")?)?
+    );
+    let start = Instant::now();
+    let d6_part1_solution = d6_part1(&parse_input("inputs/2021/d6")?)?;
+    let duration = start.elapsed();
+    println!(
+        "number of fish after 80 days: {} (duration {:?})",
+        d6_part1_solution, duration
+    );
+
+    println!(
+        "[test] number of fish after 256 days: {}",
+        d6_part2(&parse_input("inputs/2021/d6_test")?)?
+    );
+    let start2 = Instant::now();
+    let d6_part2_solution = d6_part2(&parse_input("inputs/2021/d6")?)?;
+    let duration2 = start2.elapsed();
+    println!(
+        "number of fish after 256 days: {} (duration {:?})",
+        d6_part2_solution, duration2
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d6_answers() {
+        manifest::assert_answers(2021, 6);
+    }
+
+    #[test]
+    fn test_d6_errors() {
+        assert!(matches!(d6_part1(&[3, 9]), Err(AocError::InvalidState(_))));
+
+        assert_eq!(parse_input_from_string("3,4\n\n3,1,2\n").unwrap(), vec![3, 4, 3, 1, 2]);
+        let e = parse_input_from_string("3,4\n3,x,2\n1,1\n9,1\n").unwrap_err();
+        let lines: Vec<_> = e.errors().iter().map(|e| e.line()).collect();
+        assert_eq!(lines, vec![Some(2), Some(4)]);
+    }
+
+    #[test]
+    fn test_fish_map_from_reader() {
+        let expected = init_fish_map(&[3, 4, 3, 1, 2]).unwrap();
+        assert_eq!(fish_map_from_reader("3,4,3,1,2\n".as_bytes()).unwrap(), expected);
+        assert_eq!(fish_map_from_reader("\n3, 4\n\n3,1,2".as_bytes()).unwrap(), expected);
+        let long = format!("1,{}", "2".repeat(100));
+        for (bad, line) in [("3,,4", 1), ("3,4,\n", 1), ("3\n4,9", 2), (long.as_str(), 1)] {
+            let result = fish_map_from_reader(bad.as_bytes());
+            assert!(matches!(result, Err(AocError::Parse { line: l, .. }) if l == line), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_populations() {
+        let maps = populations(&[3, 4, 3, 1, 2], 18).unwrap();
+        assert_eq!(maps.len(), 19);
+        assert_eq!(maps[0], [0, 1, 1, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(count_fish(maps[18]).unwrap(), 26);
+    }
+
+    #[test]
+    fn test_d6_overflow() {
+        // each of these fish spawns another on the first day
+        let fish_map = [u64::MAX / 2 + 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(count_after(fish_map, 0).unwrap(), u64::MAX / 2 + 1);
+        assert!(matches!(count_after(fish_map, 1), Err(AocError::Unsolvable(_))));
+        let fish_map = [0, 0, 0, 0, 0, 0, 0, 0, u64::MAX];
+        assert!(matches!(count_after(fish_map, 9), Err(AocError::Unsolvable(_))));
+        let fish_map = [1, 0, 0, 0, 0, 0, 0, 0, u64::MAX];
+        assert!(matches!(count_fish(fish_map), Err(AocError::Unsolvable(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(timers in prop::collection::vec(0..=MAX_TIMER, 1..50)) {
+            let text = timers.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(",");
+            let fish_map = fish_map_from_reader(text.as_bytes()).unwrap();
+            prop_assert_eq!(fish_map, init_fish_map(&timers).unwrap());
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), timers);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/// AoC 2021 -- Day 7
+/// https://adventofcode.com/2021/day/7
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::util;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 7;
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64> {
+        d7_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64> {
+        d7_part2(input)
+    }
+}
+
+pub fn parse_input(input_file: &str) -> Result<Vec<u64>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<u64>> {
+    let mut xs = Vec::new();
+    for (n, line) in util::numbered_lines(content) {
+        for s in line.split(',') {
+            xs.push(util::parse_field::<u64>(line, s, n)?);
+        }
+    }
+    Ok(xs)
+}
+
+/// This is not correct, in general, but is for the two given inputs :)
+fn median(xs: &[u64]) -> u64 {
+    let mut xs_copy = xs.to_owned();
+    xs_copy.sort_unstable();
+    let n = xs_copy.len();
+    if n.is_multiple_of(2) {
+        xs_copy[(n / 2) - 1]
+    } else {
+        xs_copy[n / 2]
+    }
+}
+
+/// Solution for part 1 uses the fact that any median of a discrete
+/// set of real numbers minimizes the sum of absolute variation.
+pub fn d7_part1(xs: &[u64]) -> Result<u64> {
+    if xs.is_empty() {
+        return Err(AocError::unsolvable("no crab positions"));
+    }
+    let x = median(xs);
+    xs.iter()
+        .try_fold(0u64, |fuel, &n| fuel.checked_add(n.abs_diff(x)))
+        .ok_or_else(overflow)
+}
+
+/// Solution for part 2 uses the identity:
+/// 1 + 2 + ... + n = (n + 1) * n / 2
+///
+/// so the fuel is half the sum of squared distances plus half the sum of absolute distances,
+/// which is minimized within 1/2 of the mean. Only the positions around the mean are tried,
+/// rather than every position between the crabs.
+pub fn d7_part2(xs: &[u64]) -> Result<u64> {
+    if xs.is_empty() {
+        return Err(AocError::unsolvable("no crab positions"));
+    }
+    let sum: u128 = xs.iter().map(|&n| n as u128).sum();
+    // the floor of the mean is never more than the largest position, so it fits in a u64
+    let mean = (sum / xs.len() as u128) as u64;
+    // a position whose fuel overflows can't be the minimum unless they all overflow
+    (mean.saturating_sub(1)..=mean.saturating_add(2))
+        .filter_map(|x| fuel_to(xs, x))
+        .min()
+        .ok_or_else(overflow)
+}
+
+/// Fuel for every crab in `xs` to move to `x` in part 2, or None if it overflows a u64
+fn fuel_to(xs: &[u64], x: u64) -> Option<u64> {
+    xs.iter().try_fold(0u64, |fuel, &n| {
+        let d = n.abs_diff(x) as u128;
+        // increment by 1 + 2 + ... + d
+        fuel.checked_add(u64::try_from((d + 1) * d / 2).ok()?)
+    })
+}
+
+fn overflow() -> AocError {
+    AocError::unsolvable("the fuel overflows a u64")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_d7_answers() {
+        manifest::assert_answers(2021, 7);
+    }
+
+    #[test]
+    fn test_parse_inline() {
+        let inline = parse_input_from_string("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(inline, parse_input("inputs/2021/d7_test").unwrap());
+    }
+
+    #[test]
+    fn test_d7_wide() {
+        let xs = parse_input_from_string("0,4000000000").unwrap();
+        assert_eq!(d7_part1(&xs).unwrap(), 4000000000);
+        assert_eq!(d7_part2(&xs).unwrap(), 4000000002000000000);
+        let xs = parse_input_from_string("0,18446744073709551615").unwrap();
+        assert_eq!(d7_part1(&xs).unwrap(), u64::MAX);
+        assert!(matches!(d7_part2(&xs), Err(AocError::Unsolvable(_))));
+        let xs = parse_input_from_string("0,0,18446744073709551615,18446744073709551615").unwrap();
+        assert!(matches!(d7_part1(&xs), Err(AocError::Unsolvable(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(xs in prop::collection::vec(any::<u64>(), 1..50)) {
+            let text = xs.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), xs);
+        }
+
+        #[test]
+        fn test_part2_matches_full_scan(xs in prop::collection::vec(0u64..2000, 1..50)) {
+            let (xmin, xmax) = (*xs.iter().min().unwrap(), *xs.iter().max().unwrap());
+            let scanned = (xmin..=xmax).filter_map(|x| fuel_to(&xs, x)).min().unwrap();
+            prop_assert_eq!(d7_part2(&xs).unwrap(), scanned);
+        }
+    }
+}
@@ -1,50 +1,96 @@
-use crate::util;
 /// AoC 2021 -- Day 8
 /// https://adventofcode.com/2021/day/8
 use std::char;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug)]
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
+use crate::util;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 8;
+    type Input = Vec<Note>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        d8_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        d8_part2(input)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
 pub struct Note {
     obs: Vec<String>,
     outputs: Vec<String>,
 }
 
 impl FromStr for Note {
-    type Err = ();
+    type Err = AocError;
 
     /// Notes: observations and outputs are sorted alphabetically in order to
     /// canonicallize the rep'n
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut vert_splits = s.split('|');
-        let prefix = vert_splits.next().ok_or(())?.trim();
-        let suffix = vert_splits.next().ok_or(())?.trim();
-        let obs = prefix
-            .split(char::is_whitespace)
-            .map(|s| normalize(s))
-            .collect();
-        let outputs = suffix
-            .split(char::is_whitespace)
-            .map(|s| normalize(s))
-            .collect();
+    fn from_str(s: &str) -> Result<Self> {
+        let (prefix, suffix) = s
+            .split_once('|')
+            .ok_or_else(|| {
+                AocError::parse(0, "expected observations | outputs").expected("a `|`")
+            })?;
+        let obs = parse_patterns(prefix, 10).map_err(|e| e.at(s, prefix))?;
+        let outputs = parse_patterns(suffix, 4).map_err(|e| e.at(s, suffix))?;
         Ok(Note { obs, outputs })
     }
 }
 
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} | {}", self.obs.join(" "), self.outputs.join(" "))
+    }
+}
+
+/// Parse exactly `count` whitespace separated, normalized signal patterns
+fn parse_patterns(s: &str, count: usize) -> Result<Vec<String>> {
+    let patterns: Vec<String> = s.split_whitespace().map(normalize).collect();
+    if patterns.len() != count {
+        return Err(AocError::parse(
+            0,
+            format!("expected {} signal patterns, found {}", count, patterns.len()),
+        ));
+    }
+    if let Some(p) = patterns
+        .iter()
+        .find(|p| !p.chars().all(|c| ('a'..='g').contains(&c)))
+    {
+        return Err(AocError::parse(0, format!("invalid signal pattern: {}", p)));
+    }
+    Ok(patterns)
+}
+
 fn normalize(s: &str) -> String {
     let mut cs: Vec<char> = s.chars().collect();
     cs.sort_unstable();
     cs.into_iter().collect()
 }
 
-pub fn parse_input(input_file: &str) -> Vec<Note> {
-    let content = util::read_to_string(input_file).unwrap();
-    content
-        .trim()
-        .split('\n')
-        .map(|s| s.parse::<Note>().expect("failed to parse Note from input"))
-        .collect()
+pub fn parse_input(input_file: &str) -> Result<Vec<Note>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Vec<Note>> {
+    util::parse_lines(content, |n, s| s.parse::<Note>().map_err(|e| e.at_line(n)))
 }
 
 fn is_uniq_output(output: &str) -> bool {
@@ -52,7 +98,7 @@ fn is_uniq_output(output: &str) -> bool {
     n == 2 || n == 3 || n == 4 || n == 7
 }
 
-pub fn d8_part1(notes: &[Note]) -> usize {
+pub fn d8_part1(notes: &[Note]) -> Result<usize> {
     let mut count: usize = 0;
     for n in notes {
         for out in n.outputs.iter() {
@@ -61,7 +107,7 @@ pub fn d8_part1(notes: &[Note]) -> usize {
             }
         }
     }
-    count
+    Ok(count)
 }
 
 type SignalSet = String;
@@ -107,7 +153,7 @@ fn infer(
 /// .    f  e    f  .    f  e    f  .    f
 ///  gggg    gggg    ....    gggg    gggg
 ///
-fn infer_signal_mapping(note: &Note) -> HashMap<SignalSet, u32> {
+fn infer_signal_mapping(note: &Note) -> Result<HashMap<SignalSet, u32>> {
     let mut sig_map: HashMap<SignalSet, u32> = HashMap::new();
     let mut digit_map: HashMap<u32, SignalSet> = HashMap::new();
     let obs_sets: Vec<SignalSet> = note
@@ -139,11 +185,11 @@ fn infer_signal_mapping(note: &Note) -> HashMap<SignalSet, u32> {
     }
     let sig_1 = digit_map
         .get(&1)
-        .expect("failed to find signal for 1")
+        .ok_or_else(|| AocError::unsolvable("failed to find signal for 1"))?
         .clone();
     let sig_4 = digit_map
         .get(&4)
-        .expect("failed to find signal for 1")
+        .ok_or_else(|| AocError::unsolvable("failed to find signal for 4"))?
         .clone();
 
     // second pass: infer signal patterns of length 5, 6
@@ -164,7 +210,10 @@ fn infer_signal_mapping(note: &Note) -> HashMap<SignalSet, u32> {
                 } else if common_with_4 == 3 {
                     infer(&mut sig_map, &mut digit_map, obs, 5);
                 } else {
-                    panic!("5-signal inference failed for: {}", obs);
+                    return Err(AocError::unsolvable(format!(
+                        "5-signal inference failed for: {}",
+                        obs
+                    )));
                 }
             }
 
@@ -180,10 +229,10 @@ fn infer_signal_mapping(note: &Note) -> HashMap<SignalSet, u32> {
                 } else if common_with_4 == 4 {
                     infer(&mut sig_map, &mut digit_map, obs, 9);
                 } else {
-                    panic!(
-                        "5-signal inference failed for: {}, digit_map: {:?}",
+                    return Err(AocError::unsolvable(format!(
+                        "6-signal inference failed for: {}, digit_map: {:?}",
                         obs, digit_map
-                    );
+                    )));
                 }
             }
 
@@ -194,57 +243,61 @@ fn infer_signal_mapping(note: &Note) -> HashMap<SignalSet, u32> {
     }
 
     if digit_map.len() != 10 {
-        panic!("could not infer all digits, digit_map: {:?}", digit_map);
+        return Err(AocError::unsolvable(format!(
+            "could not infer all digits, digit_map: {:?}",
+            digit_map
+        )));
     }
-    sig_map
+    Ok(sig_map)
 }
 
-fn decode_outputs(sig_map: &HashMap<SignalSet, u32>, outputs: &[String]) -> u32 {
+fn decode_outputs(sig_map: &HashMap<SignalSet, u32>, outputs: &[String]) -> Result<u32> {
     let mut result = 0u32;
     for out_sig in outputs {
-        let digit = sig_map.get(out_sig).unwrap_or_else(||
-            panic!("could not find pattern: {} in signal map: {:?}", out_sig, sig_map));
+        let digit = sig_map.get(out_sig).ok_or_else(|| {
+            AocError::unsolvable(format!(
+                "could not find pattern: {} in signal map: {:?}",
+                out_sig, sig_map
+            ))
+        })?;
         result = result * 10 + digit;
     }
-    result
+    Ok(result)
 }
 
-pub fn d8_part2(notes: &[Note]) -> u32 {
+pub fn d8_part2(notes: &[Note]) -> Result<u32> {
     let mut solution = 0u32;
     for n in notes.iter() {
-        let sig_map = infer_signal_mapping(n);
-        let decoded_output = decode_outputs(&sig_map, &n.outputs);
+        let sig_map = infer_signal_mapping(n)?;
+        let decoded_output = decode_outputs(&sig_map, &n.outputs)?;
         solution += decoded_output;
     }
-    solution
+    Ok(solution)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
 
     #[test]
     fn test_parser_input_d8_test() {
-        let notes = parse_input("inputs/d8_test");
+        let notes = parse_input("inputs/2021/d8_test").unwrap();
         assert_eq!(notes.len(), 10);
     }
 
     #[test]
     fn test_parser_input_d8() {
-        let notes = parse_input("inputs/d8");
+        let notes = parse_input("inputs/2021/d8").unwrap();
         assert_eq!(notes.len(), 200);
     }
 
     #[test]
-    fn test_d8_part1_test() {
-        let notes = parse_input("inputs/d8_test");
-        assert_eq!(d8_part1(&notes), 26);
-    }
-
-    #[test]
-    fn test_d8_part1() {
-        let notes = parse_input("inputs/d8");
-        assert_eq!(d8_part1(&notes), 416);
+    #[cfg(feature = "tools")]
+    fn test_d8_answers() {
+        manifest::assert_answers(2021, 8);
     }
 
     #[test]
@@ -268,21 +321,38 @@ mod test {
         .collect();
 
         let note = test_input.parse::<Note>().unwrap();
-        let sig_map = infer_signal_mapping(&note);
+        let sig_map = infer_signal_mapping(&note).unwrap();
         assert_eq!(sig_map, expected_mapping);
 
-        assert_eq!(decode_outputs(&sig_map, &note.outputs), 5353);
+        assert_eq!(decode_outputs(&sig_map, &note.outputs).unwrap(), 5353);
     }
 
     #[test]
-    fn test_d8_part2_test() {
-        let notes = parse_input("inputs/d8_test");
-        assert_eq!(d8_part2(&notes), 61229);
+    fn test_parse_note_errors() {
+        assert!("acedgfb cdfbe gcdfa fbcad dab".parse::<Note>().is_err());
+        assert!("acedgfb | cdfeb fcadb cdfeb cdbaf".parse::<Note>().is_err());
+        let bad_char = "xcedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        assert!(bad_char.parse::<Note>().is_err());
     }
 
     #[test]
-    fn test_d8_part2() {
-        let notes = parse_input("inputs/d8");
-        assert_eq!(d8_part2(&notes), 1043697);
+    fn test_infer_unsolvable() {
+        // every observation is the same pattern, so nothing can be inferred
+        let note = "ab ab ab ab ab ab ab ab ab ab | ab ab ab ab".parse::<Note>().unwrap();
+        assert!(matches!(infer_signal_mapping(&note), Err(AocError::Unsolvable(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            obs in prop::collection::vec("[a-g]{1,7}", 10),
+            outputs in prop::collection::vec("[a-g]{1,7}", 4),
+        ) {
+            let text = format!("{} | {}", obs.join(" "), outputs.join(" "));
+            let note = text.parse::<Note>().unwrap();
+            prop_assert_eq!(&note.obs, &obs.iter().map(|o| normalize(o)).collect::<Vec<String>>());
+            prop_assert_eq!(&note.outputs, &outputs.iter().map(|o| normalize(o)).collect::<Vec<String>>());
+            prop_assert_eq!(note.to_string().parse::<Note>().unwrap(), note);
+        }
     }
 }
@@ -2,9 +2,33 @@
 /// https://adventofcode.com/2021/day/9
 use std::collections::HashSet;
 
+use crate::error::{AocError, Result};
+use crate::solution::Solution;
 use crate::{grid::Grid, util};
 
-type Height = u32;
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 9;
+    type Input = Grid<Height>;
+    type Answer1 = Risk;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input> {
+        parse_input_from_string(content)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Risk> {
+        d9_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        d9_part2(input)
+    }
+}
+
+pub type Height = u32;
 const MAX_HEIGHT: u32 = 9;
 type Risk = u32;
 
@@ -43,14 +67,13 @@ fn collect_neigbors(
     nblocs.push((row, col + 1));
     nblocs
         .into_iter()
-        .map(|(r, c)| {
+        .filter_map(|(r, c)| {
             grid.get(r, c).map(|&v| Pos {
                 row: r,
                 col: c,
                 value: v,
             })
         })
-        .flatten()
         .filter(|p| p.value >= min_height && p.value <= max_height)
         .collect()
 }
@@ -73,14 +96,24 @@ pub fn windows(grid: &Grid<Height>) -> Vec<Basin> {
     result
 }
 
-pub fn parse_input(input_file: &str) -> Grid<Height> {
-    let content = util::read_to_string(input_file).unwrap();
-    let rows: Vec<Vec<Height>> = content
-        .trim()
-        .split('\n')
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect();
-    Grid::from_rows(rows).expect("failed to build grid")
+pub fn parse_input(input_file: &str) -> Result<Grid<Height>> {
+    let content = util::read_to_string(input_file)?;
+    parse_input_from_string(&content)
+}
+
+pub fn parse_input_from_string(content: &str) -> Result<Grid<Height>> {
+    let rows: Vec<Vec<Height>> = util::parse_lines(content, |n, line| {
+        line.char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10).ok_or_else(|| {
+                    AocError::parse(n, format!("invalid height: {:?}", c))
+                        .at(line, &line[i..i + c.len_utf8()])
+                        .expected("a digit")
+                })
+            })
+            .collect()
+    })?;
+    Grid::from_rows(rows).map_err(|e| AocError::parse(0, e))
 }
 
 fn find_low_points(grid: &Grid<Height>) -> Area {
@@ -93,12 +126,12 @@ fn find_low_points(grid: &Grid<Height>) -> Area {
 }
 
 /// returns the sum of low point risk scores
-pub fn d9_part1(grid: &Grid<Height>) -> Risk {
-    find_low_points(grid).iter().map(|p| 1 + p.value).sum()
+pub fn d9_part1(grid: &Grid<Height>) -> Result<Risk> {
+    Ok(find_low_points(grid).iter().map(|p| 1 + p.value).sum())
 }
 
 /// returns the combined size of the top 3 largest basins
-pub fn d9_part2(grid: &Grid<Height>) -> usize {
+pub fn d9_part2(grid: &Grid<Height>) -> Result<usize> {
     let low_points = find_low_points(grid);
     let mut basins: Vec<(Basin, Area)> = low_points
         .into_iter()
@@ -149,46 +182,51 @@ pub fn d9_part2(grid: &Grid<Height>) -> usize {
         .collect::<Vec<usize>>();
     final_basin_sizes.sort_unstable();
     final_basin_sizes = final_basin_sizes.into_iter().rev().collect();
-    assert!(final_basin_sizes.len() >= 3);
-    final_basin_sizes[0] * final_basin_sizes[1] * final_basin_sizes[2]
+    if final_basin_sizes.len() < 3 {
+        return Err(AocError::unsolvable(format!(
+            "found {} basins, need at least 3",
+            final_basin_sizes.len()
+        )));
+    }
+    Ok(final_basin_sizes[0] * final_basin_sizes[1] * final_basin_sizes[2])
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    #[cfg(feature = "tools")]
+    use crate::manifest;
 
     #[test]
     fn test_parser() {
-        let test_grid = parse_input("inputs/d9_test");
+        let test_grid = parse_input("inputs/2021/d9_test").unwrap();
         assert_eq!(test_grid.rows, 5);
         assert_eq!(test_grid.cols, 10);
 
-        let grid = parse_input("inputs/d9");
+        let grid = parse_input("inputs/2021/d9").unwrap();
         assert_eq!(grid.rows, 100);
         assert_eq!(grid.cols, 100);
     }
 
     #[test]
-    fn test_d9_part1_test() {
-        let test_grid = parse_input("inputs/d9_test");
-        assert_eq!(d9_part1(&test_grid), 15);
-    }
-
-    #[test]
-    fn test_d9_part1() {
-        let grid = parse_input("inputs/d9");
-        assert_eq!(d9_part1(&grid), 588);
-    }
-
-    #[test]
-    fn test_d9_part2_test() {
-        let test_grid = parse_input("inputs/d9_test");
-        assert_eq!(d9_part2(&test_grid), 1134);
+    #[cfg(feature = "tools")]
+    fn test_d9_answers() {
+        manifest::assert_answers(2021, 9);
     }
 
-    #[test]
-    fn test_d9_part2() {
-        let grid = parse_input("inputs/d9");
-        assert_eq!(d9_part2(&grid), 964712);
+    proptest! {
+        #[test]
+        fn test_parse_round_trip(
+            rows in (1usize..12).prop_flat_map(|w| {
+                prop::collection::vec(prop::collection::vec(0u32..10, w), 1..12)
+            })
+        ) {
+            let text: String = rows
+                .iter()
+                .map(|r| r.iter().map(|v| v.to_string()).collect::<String>() + "\n")
+                .collect();
+            prop_assert_eq!(parse_input_from_string(&text).unwrap(), Grid::from_rows(rows).unwrap());
+        }
     }
 }
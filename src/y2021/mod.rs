@@ -0,0 +1,27 @@
+/// Advent of Code 2021: one module per day, each built with its `y2021-d<DAY>` cargo feature
+#[cfg(feature = "y2021-d1")]
+pub mod d1;
+#[cfg(feature = "y2021-d2")]
+pub mod d2;
+#[cfg(feature = "y2021-d3")]
+pub mod d3;
+#[cfg(feature = "y2021-d4")]
+pub mod d4;
+#[cfg(feature = "y2021-d5")]
+pub mod d5;
+#[cfg(feature = "y2021-d6")]
+pub mod d6;
+#[cfg(feature = "y2021-d7")]
+pub mod d7;
+#[cfg(feature = "y2021-d8")]
+pub mod d8;
+#[cfg(feature = "y2021-d9")]
+pub mod d9;
+#[cfg(feature = "y2021-d10")]
+pub mod d10;
+#[cfg(feature = "y2021-d11")]
+pub mod d11;
+#[cfg(feature = "y2021-d12")]
+pub mod d12;
+#[cfg(feature = "y2021-d13")]
+pub mod d13;